@@ -1,6 +1,5 @@
 fn input() -> String {
-    std::fs::read_to_string("input/day01.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 1)
 }
 
 fn main() {
@@ -13,8 +13,7 @@ use nom::{
 use std::ops::Add;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day02.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 2)
 }
 
 fn main() {
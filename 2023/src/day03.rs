@@ -2,8 +2,7 @@ use std::collections::HashSet;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day03.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 3)
 }
 
 fn main() {
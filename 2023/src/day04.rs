@@ -6,8 +6,7 @@ use nom::multi::separated_list1;
 use nom::sequence::tuple;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day04.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 4)
 }
 
 fn main() {
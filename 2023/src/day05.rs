@@ -10,8 +10,7 @@ use nom::multi::separated_list1;
 use nom::sequence::tuple;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day05.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 5)
 }
 
 fn main() {
@@ -2,8 +2,7 @@ use itertools::{iproduct, Itertools};
 use nom::ParseTo;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day07.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 7)
 }
 
 fn main() {
@@ -3,8 +3,7 @@ use itertools::Itertools;
 use regex::Regex;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day08.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2023, 8)
 }
 
 fn main() {
@@ -0,0 +1,125 @@
+// Benchmarks the core PointSet operations for different grid sizes, and compares some of them with
+// SparsePointSet, which stores the points in a HashSet.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use utils::{Dimensions, Direction, IPoint, Point, PointSet, SparsePointSet, Topology};
+
+// Square grids, from smaller than a word to larger than the typical puzzle input (141 x 141)
+const SIZES: [usize; 4] = [16, 64, 141, 512];
+
+// About a third of the points in a fixed, irregular pattern, like the walls in a maze
+fn walls(size: usize) -> PointSet {
+    let dimensions = Dimensions {
+        width: size,
+        height: size,
+    };
+
+    PointSet::from_points(
+        &dimensions,
+        (0..size)
+            .flat_map(|y| (0..size).map(move |x| Point { x, y }))
+            .filter(|Point { x, y }| (x * 7 + y * 13 + x * y) % 3 == 0),
+    )
+}
+
+fn sparse(point_set: &PointSet) -> SparsePointSet {
+    SparsePointSet::from_point_set(point_set, IPoint { x: 0, y: 0 })
+}
+
+fn bench_operations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PointSet");
+
+    for size in SIZES {
+        let walls = walls(size);
+        let free = !&walls;
+
+        group.bench_with_input(BenchmarkId::new("shift", size), &walls, |b, walls| {
+            b.iter(|| walls.shift(black_box(Direction::Right)))
+        });
+        group.bench_with_input(BenchmarkId::new("translate", size), &walls, |b, walls| {
+            b.iter(|| walls.translate(black_box(5), black_box(-3)))
+        });
+        group.bench_with_input(BenchmarkId::new("shake", size), &walls, |b, walls| {
+            b.iter(|| walls.shake())
+        });
+        group.bench_with_input(BenchmarkId::new("shake8", size), &walls, |b, walls| {
+            b.iter(|| walls.shake8())
+        });
+        group.bench_with_input(BenchmarkId::new("and", size), &walls, |b, walls| {
+            b.iter(|| walls & &free)
+        });
+        group.bench_with_input(BenchmarkId::new("not", size), &walls, |b, walls| {
+            b.iter(|| !walls)
+        });
+        group.bench_with_input(BenchmarkId::new("len", size), &walls, |b, walls| {
+            b.iter(|| walls.len())
+        });
+        group.bench_with_input(BenchmarkId::new("iter", size), &walls, |b, walls| {
+            b.iter(|| walls.iter().map(|p| p.x).sum::<usize>())
+        });
+
+        // dilate_manhattan(r) is the same as shaking r times
+        for (topology, walls) in [
+            ("bounded", walls.clone()),
+            ("toroidal", walls.clone().with_topology(Topology::Toroidal)),
+        ] {
+            for r in [3, 8, 20] {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("dilate_manhattan/{}/{}", topology, r), size),
+                    &walls,
+                    |b, walls| b.iter(|| walls.dilate_manhattan(black_box(r))),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new(format!("shake_repeatedly/{}/{}", topology, r), size),
+                    &walls,
+                    |b, walls| {
+                        b.iter(|| (0..black_box(r)).fold(walls.clone(), |ps, _| ps.shake()))
+                    },
+                );
+            }
+        }
+
+        let start = free.first().unwrap();
+        group.bench_with_input(BenchmarkId::new("component", size), &free, |b, free| {
+            b.iter(|| free.component_containing(&start))
+        });
+        group.bench_with_input(BenchmarkId::new("distance_map", size), &free, |b, free| {
+            b.iter(|| PointSet::from_point(&free.dimensions, &start).distance_map(free))
+        });
+    }
+}
+
+fn bench_sparse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("SparsePointSet");
+
+    for size in SIZES {
+        let walls = sparse(&walls(size));
+        let free = sparse(&!&self::walls(size));
+
+        group.bench_with_input(BenchmarkId::new("shift", size), &walls, |b, walls| {
+            b.iter(|| walls.shift(black_box(Direction::Right)))
+        });
+        group.bench_with_input(BenchmarkId::new("translate", size), &walls, |b, walls| {
+            b.iter(|| walls.translate(black_box(5), black_box(-3)))
+        });
+        group.bench_with_input(BenchmarkId::new("shake", size), &walls, |b, walls| {
+            b.iter(|| walls.shake())
+        });
+        group.bench_with_input(BenchmarkId::new("shake8", size), &walls, |b, walls| {
+            b.iter(|| walls.shake8())
+        });
+        group.bench_with_input(BenchmarkId::new("and", size), &walls, |b, walls| {
+            b.iter(|| walls & &free)
+        });
+        group.bench_with_input(BenchmarkId::new("len", size), &walls, |b, walls| {
+            b.iter(|| walls.len())
+        });
+        group.bench_with_input(BenchmarkId::new("iter", size), &walls, |b, walls| {
+            b.iter(|| walls.iter().map(|p| p.x).sum::<i64>())
+        });
+    }
+}
+
+criterion_group!(benches, bench_operations, bench_sparse);
+criterion_main!(benches);
@@ -0,0 +1,120 @@
+// Compares the different implementations of the same puzzle. The solutions are binaries, so their
+// source is included into a module, and the functions of interest are wrapped to make them
+// visible here.
+//
+// Each variant is run on the example from the puzzle description (benches/fixtures) and, unless it
+// is too slow, on the real input.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+#[allow(unused, clippy::all)]
+mod day13 {
+    include!("../src/day13.rs");
+
+    pub fn brute_force(data: &str) -> i64 {
+        part1(data)
+    }
+
+    pub fn efficient(data: &str) -> i64 {
+        part1_efficient(data)
+    }
+}
+
+#[allow(unused, clippy::all)]
+mod day19 {
+    include!("../src/day19.rs");
+
+    pub fn naive(data: &str) -> usize {
+        part1_naive(data)
+    }
+
+    pub fn efficient(data: &str) -> usize {
+        part1(data)
+    }
+}
+
+#[allow(unused, clippy::all)]
+mod day20 {
+    include!("../src/day20.rs");
+
+    pub fn efficient(data: &str) -> usize {
+        part1(&parse(data))
+    }
+}
+
+#[allow(unused, clippy::all)]
+mod day20_pointset {
+    include!("../src/day20-pointset.rs");
+
+    pub fn naive(data: &str) -> usize {
+        part1_naive(&parse(data))
+    }
+
+    pub fn efficient(data: &str) -> usize {
+        part1(&parse(data))
+    }
+}
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|_| panic!("Could not read {}", path))
+}
+
+fn inputs(day: u32) -> [(&'static str, String); 2] {
+    [
+        (
+            "example",
+            read(&format!("benches/fixtures/day{:02}.txt", day)),
+        ),
+        ("input", aoc_core::input::read(2024, day)),
+    ]
+}
+
+fn bench_day13(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day13 part 1");
+
+    for (name, data) in inputs(13) {
+        group.bench_with_input(BenchmarkId::new("brute force", name), &data, |b, data| {
+            b.iter(|| day13::brute_force(data))
+        });
+        group.bench_with_input(BenchmarkId::new("efficient", name), &data, |b, data| {
+            b.iter(|| day13::efficient(data))
+        });
+    }
+}
+
+fn bench_day19(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day19 part 1");
+
+    for (name, data) in inputs(19) {
+        group.bench_with_input(BenchmarkId::new("efficient", name), &data, |b, data| {
+            b.iter(|| day19::efficient(data))
+        });
+    }
+
+    // The naive solution does not finish in reasonable time for the real input
+    let example = read("benches/fixtures/day19.txt");
+    group.bench_function("naive/example", |b| b.iter(|| day19::naive(&example)));
+}
+
+fn bench_day20(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day20 part 1");
+    group.sample_size(10);
+
+    for (name, data) in inputs(20) {
+        group.bench_with_input(BenchmarkId::new("default", name), &data, |b, data| {
+            b.iter(|| day20::efficient(data))
+        });
+        group.bench_with_input(BenchmarkId::new("pointset", name), &data, |b, data| {
+            b.iter(|| day20_pointset::efficient(data))
+        });
+    }
+
+    // The naive pointset solution is too slow for the real input
+    let example = read("benches/fixtures/day20.txt");
+    group.bench_function("pointset naive/example", |b| {
+        b.iter(|| day20_pointset::naive(&example))
+    });
+}
+
+criterion_group!(benches, bench_day13, bench_day19, bench_day20);
+criterion_main!(benches);
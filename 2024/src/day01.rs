@@ -1,8 +1,7 @@
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day01.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 1)
 }
 
 fn main() {
@@ -2,8 +2,7 @@ use std::str::FromStr;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day02.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 2)
 }
 
 fn main() {
@@ -1,8 +1,7 @@
 use regex::Regex;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day03.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 3)
 }
 
 fn main() {
@@ -3,8 +3,7 @@ use std::collections::HashSet;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day05.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 5)
 }
 
 fn main() {
@@ -3,7 +3,7 @@ use std::iter::from_fn;
 use std::ops::Add;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day06.txt").expect("Could not read file")
+    aoc_core::input::read(2024, 6)
 }
 
 fn main() {
@@ -1,8 +1,7 @@
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day07.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 7)
 }
 
 fn main() {
@@ -66,12 +65,12 @@ fn could_be_true_2(result: u64, numbers: &[u64]) -> bool {
     let last = numbers[numbers.len() - 1];
     let rest = &numbers[0..(numbers.len() - 1)];
 
-    (result >= last && crate::could_be_true_2(result - last, rest))
-        || (result % last == 0 && crate::could_be_true_2(result / last, rest))
+    (result >= last && could_be_true_2(result - last, rest))
+        || (result % last == 0 && could_be_true_2(result / last, rest))
         ||
         {
             if let Some(new_result) = remove_suffix(result, last) {
-                crate::could_be_true_2(new_result, rest)
+                could_be_true_2(new_result, rest)
             } else {
                 false
             }
@@ -2,8 +2,7 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day08.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 8)
 }
 
 fn main() {
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day09.txt").expect("Could not read file")
+    aoc_core::input::read(2024, 9)
 }
 
 fn main() {
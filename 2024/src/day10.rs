@@ -1,8 +1,7 @@
 use std::collections::HashSet;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day10.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 10)
 }
 
 fn main() {
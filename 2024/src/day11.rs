@@ -2,8 +2,7 @@ use std::collections::HashMap;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day11.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 11)
 }
 
 fn main() {
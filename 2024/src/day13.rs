@@ -2,8 +2,7 @@ use itertools::Itertools;
 use regex::Regex;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day13.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 13)
 }
 
 fn main() {
@@ -3,18 +3,27 @@ use itertools::Itertools;
 use regex::Regex;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day14.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 14)
 }
 
 fn main() {
-    let data = input();
+    let robots = parse(&input());
 
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+    println!("Part 1: {}", part1(&robots));
+    let second = part2(&robots);
+    println!("Part 2: {}", second);
+
+    // The robots show a picture of a Christmas tree at that second
+    let positions = robots.iter()
+        .map(|robot| evolve(robot, 101, 103, second as i32))
+        .collect();
+    print_robots(101, 103, &positions);
 }
 
-fn parse(data: &str) -> Vec<((i32, i32), (i32, i32))> {
+// The initial position and the velocity
+type Robot = ((i32, i32), (i32, i32));
+
+fn parse(data: &str) -> Vec<Robot> {
     let robot_regex = Regex::new(r"^p=([0-9]+),([0-9]+) v=(-?[0-9]+),(-?[0-9]+)$").unwrap();
 
     data.lines()
@@ -32,7 +41,7 @@ fn parse(data: &str) -> Vec<((i32, i32), (i32, i32))> {
         .collect()
 }
 
-fn evolve(robot: &((i32, i32), (i32, i32)), width: usize, height: usize, seconds: i32) -> (i32, i32) {
+fn evolve(robot: &Robot, width: usize, height: usize, seconds: i32) -> (i32, i32) {
     let ((x0, y0), (vx, vy)) = robot;
 
     let (x, y) = (
@@ -46,12 +55,12 @@ fn evolve(robot: &((i32, i32), (i32, i32)), width: usize, height: usize, seconds
     )
 }
 
-fn part1_impl(width: usize, height: usize, seconds: i32, data: &str) -> usize {
+fn part1_impl(width: usize, height: usize, seconds: i32, robots: &[Robot]) -> usize {
     let x_middle = (width / 2) as i32;
     let y_middle = (height / 2) as i32;
 
-    let robots_in_quadrant = parse(data).into_iter()
-        .map(|robot| evolve(&robot, width, height, seconds))
+    let robots_in_quadrant = robots.iter()
+        .map(|robot| evolve(robot, width, height, seconds))
         .map(
             |(x, y)|
                 ((x - x_middle).signum(), (y - y_middle).signum())
@@ -67,8 +76,8 @@ fn part1_impl(width: usize, height: usize, seconds: i32, data: &str) -> usize {
     robots_in_quadrant.values().product()
 }
 
-fn part1(data: &str) -> usize {
-    part1_impl(101, 103, 100, data)
+fn part1(robots: &[Robot]) -> usize {
+    part1_impl(101, 103, 100, robots)
 }
 
 fn advance(width: i32, height: i32, (vx, vy): &(i32, i32), (ref mut x, ref mut y): &mut (i32, i32)) {
@@ -86,13 +95,11 @@ fn print_robots(width: usize, height: usize, robot_positions: &HashSet<(i32, i32
     }
 }
 
-fn part2(data: &str) -> usize {
+fn part2_impl(width: usize, height: usize, robots: &[Robot]) -> usize {
     // This could probably be optimized to run much faster, but the straightforward solution runs
     // in acceptable time.
 
-    let width = 101;
-    let height = 103;
-    let (mut positions, velocities): (Vec<_>, Vec<_>) = parse(data).into_iter().unzip();
+    let (mut positions, velocities): (Vec<_>, Vec<_>) = robots.iter().copied().unzip();
 
     for second in 0..width * height {
         // Check if many robots are close to another robot
@@ -117,7 +124,6 @@ fn part2(data: &str) -> usize {
 
         // Terminate if more than half the robots are close to another one
         if robots_with_neighbors > positions.len() / 2 {
-            print_robots(width, height, &all_positions);
             return second;
         }
 
@@ -130,6 +136,10 @@ fn part2(data: &str) -> usize {
     panic!("Could not find solution");
 }
 
+fn part2(robots: &[Robot]) -> usize {
+    part2_impl(101, 103, robots)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,7 +159,7 @@ p=9,5 v=-3,-3";
 
     #[test]
     fn test_part1() {
-        assert_eq!(12, part1_impl(11, 7, 100, TEST_INPUT));
+        assert_eq!(12, part1_impl(11, 7, 100, &parse(TEST_INPUT)));
     }
 
     #[test]
@@ -1,23 +1,18 @@
-#[path = "../utils/pointset.rs"]
-mod utils;
-
-use crate::utils::{parse_point_sets, point_sets_map, Direction, Point};
 use itertools::Itertools;
-use std::collections::HashSet;
-use utils::PointSet;
+use utils::{Direction, Point, PointSet, PointSets};
 
 fn input() -> String {
-    std::fs::read_to_string("input/day15.txt").expect("Could not read file")
+    aoc_core::input::read(2024, 15)
 }
 
 fn main() {
-    let data = input();
+    let puzzle = parse(&input());
 
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+    println!("Part 1: {}", part1(&puzzle));
+    println!("Part 2: {}", part2(&puzzle));
 }
 
-struct Map {
+pub struct Map {
     walls: PointSet,
     boxes: PointSet,
     robot: PointSet,
@@ -25,18 +20,13 @@ struct Map {
 
 impl Map {
     fn parse(input: &str) -> Map {
-        let mut point_sets = parse_point_sets(input);
-
-        if point_sets.keys().map(|c| *c).collect::<HashSet<_>>()
-            != HashSet::from_iter("#O@.".chars())
-        {
-            panic!("Unexpected characters in input map");
-        }
+        let mut point_sets = PointSets::parse_with_alphabet(input, "#O@.")
+            .unwrap_or_else(|error| panic!("{}", error));
 
         Map {
-            walls: point_sets.remove(&'#').unwrap(),
-            boxes: point_sets.remove(&'O').unwrap(),
-            robot: point_sets.remove(&'@').unwrap(),
+            walls: point_sets.remove('#'),
+            boxes: point_sets.remove('O'),
+            robot: point_sets.remove('@'),
         }
     }
 }
@@ -99,24 +89,18 @@ fn try_move(
 }
 
 fn gps_coordinates_sum(boxes: &PointSet) -> usize {
-    (0..boxes.dimensions.height)
-        .flat_map(|y|
-            (0..boxes.dimensions.width)
-                .map(move |x| Point { x, y })
-        )
-        .filter(|p| boxes.contains(&p))
+    boxes
+        .iter()
         .map(|Point { x, y }| 100 * y + x)
         .sum()
 }
 
-fn part1(data: &str) -> usize {
-    let (
-        Map { walls, mut boxes, mut robot},
-        instructions
-    ) = parse(data);
+fn part1((map, instructions): &(Map, Vec<Direction>)) -> usize {
+    let Map { walls, boxes, robot } = map;
+    let (mut boxes, mut robot) = (boxes.clone(), robot.clone());
 
-    for direction in instructions {
-        try_move(&mut robot, direction, &mut boxes, &walls);
+    for &direction in instructions {
+        try_move(&mut robot, direction, &mut boxes, walls);
     }
 
     gps_coordinates_sum(&boxes)
@@ -130,10 +114,6 @@ fn try_move_wide(
     all_boxes: &mut PointSet,
     walls: &PointSet,
 ) -> bool {
-    let point = object.points().into_iter().next().unwrap();
-    let x = point.x;
-    let y = point.y;
-
     let destination = object.shift(direction);
 
     // Find out if we try to move the robot or a box
@@ -185,7 +165,7 @@ fn try_move_wide(
 
     let boxes_to_move = (&target | &target.shift(Direction::Left)) & &*all_boxes;
 
-    for box_to_move in boxes_to_move.points() {
+    for box_to_move in &boxes_to_move {
         let mut box_to_move = PointSet::from_point(&all_boxes.dimensions, &box_to_move);
         if !try_move_wide(&mut box_to_move, direction, all_boxes, walls) {
             // Undo any moves of boxes at target points that were checked earlier
@@ -204,33 +184,22 @@ fn try_move_wide(
     true
 }
 
-fn part2(data: &str) -> usize {
-    let data= data.chars()
-        .map(|c| match c {
-            '#' => "##",
-            '.' => "..",
-            '@' => "@.",
-            '\n' => "\n",
-
-            // we only track the left part of the wider box for simplicity
-            'O' => "O.",
-
-            // do not touch move instructions
-            '>' => ">",
-            '<' => "<",
-            '^' => "^",
-            'v' => "v",
-
-            _ => panic!("Unexpected character in input: '{}'", c)
-        })
-        .join("");
-
-    let (
-        Map { walls, mut boxes, mut robot},
-        instructions
-    ) = parse(&data);
+// Make the map twice as wide. Walls cover both halves of their wider cells, but we only track the
+// left part of the wider boxes for simplicity.
+fn widen(Map { walls, boxes, robot }: &Map) -> Map {
+    let walls = walls.scale_x(2);
+
+    Map {
+        walls: &walls | &walls.shift(Direction::Right),
+        boxes: boxes.scale_x(2),
+        robot: robot.scale_x(2),
+    }
+}
+
+fn part2((map, instructions): &(Map, Vec<Direction>)) -> usize {
+    let Map { walls, mut boxes, mut robot } = widen(map);
 
-    for direction in instructions {
+    for &direction in instructions {
         try_move_wide(&mut robot, direction, &mut boxes, &walls);
     }
 
@@ -240,7 +209,7 @@ fn part2(data: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::point_sets_map;
+    use utils::point_sets_map;
 
     #[test]
     fn test_move_right() {
@@ -329,8 +298,8 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_part1() {
-        assert_eq!(2028, part1(TEST_INPUT_SMALL));
-        assert_eq!(10092, part1(TEST_INPUT));
+        assert_eq!(2028, part1(&parse(TEST_INPUT_SMALL)));
+        assert_eq!(10092, part1(&parse(TEST_INPUT)));
     }
 
     const TEST_INPUT_SMALL_PART2: &str = "#######
@@ -345,11 +314,11 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn debug_part2() {
-        part2(TEST_INPUT_SMALL_PART2);
+        part2(&parse(TEST_INPUT_SMALL_PART2));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(9021, part2(TEST_INPUT));
+        assert_eq!(9021, part2(&parse(TEST_INPUT)));
     }
 }
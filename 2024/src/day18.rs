@@ -1,19 +1,15 @@
 use itertools::Itertools;
-use crate::utils::{Dimensions, Point, PointSet};
-
-#[path = "../utils/pointset.rs"]
-mod utils;
+use utils::{Dimensions, Point, PointSet};
 
 fn input() -> String {
-    std::fs::read_to_string("input/day18.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 18)
 }
 
 fn main() {
-    let data = input();
+    let fallen_bytes = fallen_bytes(&input());
 
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+    println!("Part 1: {}", part1(&fallen_bytes));
+    println!("Part 2: {}", part2(&fallen_bytes));
 }
 
 fn fallen_bytes(data: &str) -> Vec<Point> {
@@ -45,18 +41,7 @@ fn grid_dimensions(positions: &Vec<Point>) -> Dimensions {
         71
     };
 
-    Dimensions{ width: size + 1, height: size}
-}
-
-// We add a blocked column at the right to prevent wrapping
-fn blocked_right_column(positions: &Vec<Point>) -> PointSet {
-    let dimensions = grid_dimensions(positions);
-
-    PointSet::from_points(
-        &dimensions,
-        (0..dimensions.height)
-            .map(|y| Point { x: dimensions.width - 1, y })
-    )
+    Dimensions{ width: size, height: size}
 }
 
 fn start_and_exit(dimensions: &Dimensions) -> (PointSet, PointSet) {
@@ -68,7 +53,7 @@ fn start_and_exit(dimensions: &Dimensions) -> (PointSet, PointSet) {
         PointSet::from_point(
             &dimensions,
             &Point {
-                x: dimensions.width - 2,  // ignore the blocked rightmost column
+                x: dimensions.width - 1,
                 y: dimensions.height - 1
             }
         )
@@ -76,76 +61,38 @@ fn start_and_exit(dimensions: &Dimensions) -> (PointSet, PointSet) {
 }
 
 // In part 1, we take only some of the falled bytes into account
-fn corrupted_locations(data: &str) -> PointSet {
-    let fallen_bytes = fallen_bytes(data);
-
-    let blocked_column = blocked_right_column(&fallen_bytes);
-
-    &PointSet::from_point_refs(
-        &blocked_column.dimensions,
+fn corrupted_locations(fallen_bytes: &Vec<Point>) -> PointSet {
+    PointSet::from_point_refs(
+        &grid_dimensions(fallen_bytes),
         fallen_bytes.iter()
-            .take(if is_test_grid(&fallen_bytes) { 12 } else { 1024 })
-    ) | &blocked_column
+            .take(if is_test_grid(fallen_bytes) { 12 } else { 1024 })
+    )
 }
 
-fn part1(data: &str) -> usize {
-    let blocked = corrupted_locations(data);
+fn part1(fallen_bytes: &Vec<Point>) -> usize {
+    let blocked = corrupted_locations(fallen_bytes);
     let safe = !&blocked;
 
     let (start, exit) = start_and_exit(&blocked.dimensions);
 
-    (1usize..).scan(start, |visited, step| {
-        *visited |= &(visited.shake() & &safe);
-        let reached_exit = !(&*visited & &exit).is_empty();
-
-        /*
-        println!("{}\n\n", point_sets_map(&vec![
-            ('#', &blocked),
-            ('x', &*visited)
-        ]));
-        */
-
-        Some((reached_exit, step))
-    })
-        .skip_while(|(reached_exit, _)| !reached_exit)
-        .map(|(_, step)| step)
-        .next()
-        .unwrap()
+    start.distance_to(&exit, &safe).unwrap()
 }
 
 fn exit_reachable(blocked: &PointSet) -> bool {
     let (start, exit) = start_and_exit(&blocked.dimensions);
 
-    (0..)
-        .scan(start, |reachable, _| {
-            let new_reachable = &reachable.shake() & &!blocked;
-
-            if new_reachable == *reachable {
-                // Cannot reach more points
-                None
-            } else {
-                *reachable = new_reachable;
-                Some(!((&exit & &reachable).is_empty()))
-            }
-        })
-        .last()
-        .unwrap()
+    start.distance_to(&exit, &!blocked).is_some()
 }
 
-fn part2(data: &str) -> String {
-    let fallen_bytes = fallen_bytes(data);
-
-    let blocked_column = blocked_right_column(&fallen_bytes);
+fn part2(fallen_bytes: &Vec<Point>) -> String {
+    let dimensions = grid_dimensions(fallen_bytes);
 
     let first_blocking_byte = (0..fallen_bytes.len()).collect::<Vec<_>>()
         .partition_point(
             |byte_index| {
-                exit_reachable(&(
-                    &blocked_column |
-                    &PointSet::from_point_refs(
-                        &blocked_column.dimensions,
-                        &fallen_bytes[..byte_index + 1]
-                    )
+                exit_reachable(&PointSet::from_point_refs(
+                    &dimensions,
+                    &fallen_bytes[..byte_index + 1]
                 ))
             }
         );
@@ -187,11 +134,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(22, part1(TEST_INPUT));
+        assert_eq!(22, part1(&fallen_bytes(TEST_INPUT)));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!("6,1", part2(TEST_INPUT));
+        assert_eq!("6,1", part2(&fallen_bytes(TEST_INPUT)));
     }
 }
@@ -2,7 +2,7 @@ use itertools::Itertools;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 fn input() -> String {
-    std::fs::read_to_string("input/day19.txt").expect("Could not read file")
+    aoc_core::input::read(2024, 19)
 }
 
 fn main() {
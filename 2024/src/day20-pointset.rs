@@ -1,23 +1,20 @@
-use crate::utils::{parse_point_sets, Dimensions, Direction, Point, PointSet};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::iter::successors;
-
-#[path = "../utils/pointset.rs"]
-mod utils;
+use utils::{Direction, Point, PointSet, PointSets};
 
 fn input() -> String {
-    std::fs::read_to_string("input/day20.txt").expect("Could not read file")
+    aoc_core::input::read(2024, 20)
 }
 
 fn main() {
-    let data = input();
+    let map = parse(&input());
 
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+    println!("Part 1: {}", part1(&map));
+    println!("Part 2: {}", part2(&map));
 }
 
-struct Map {
+pub struct Map {
     walls: PointSet,
     track: PointSet,
     start: PointSet,
@@ -25,13 +22,20 @@ struct Map {
 }
 
 fn parse(data: &str) -> Map {
-    let mut point_sets = parse_point_sets(data);
+    let mut point_sets =
+        PointSets::parse_with_alphabet(data, "#.SE").unwrap_or_else(|error| panic!("{}", error));
+
+    let dimensions = point_sets.dimensions.clone();
+    let single_point = |c| {
+        let point = point_sets.single_point(c).unwrap_or_else(|error| panic!("{}", error));
+        PointSet::from_point(&dimensions, &point)
+    };
 
-    let walls = point_sets.remove(&'#').unwrap();
-    let start = point_sets.remove(&'S').unwrap();
-    let end = point_sets.remove(&'E').unwrap();
+    let start = single_point('S');
+    let end = single_point('E');
+    let walls = point_sets.remove('#');
 
-    let track = &point_sets.remove(&'.').unwrap() | &start | &end;
+    let track = &point_sets.remove('.') | &start | &end;
 
     Map {
         walls,
@@ -42,18 +46,11 @@ fn parse(data: &str) -> Map {
 }
 
 fn shortest_path_length(map: &Map) -> usize {
-    successors(Some(map.start.clone()), |current| {
-        let new = &current.shake() & &map.track;
-
-        if (&new & &map.end).is_empty() {
-            Some(new)
-        } else {
-            None
-        }
-    })
-    .count()
+    map.start.distance_to(&map.end, &map.track).unwrap()
 }
 
+// Unlike the other searches, this one cannot use PointSet::distance_to: the cheat position may only
+// be left in the given direction, so the step is not the same for all points of the front.
 fn shortest_path_length_with_cheat(
     map: &Map,
     cheat_pos: &Point,
@@ -99,49 +96,34 @@ fn cheats(map: &Map) -> Vec<(Point, Direction)> {
 
 // For each possible time reduction, count how many distinct cheats make it possible.
 // This works for the test map, but is too slow for the real map.
-fn cheat_counts_naive(data: &str) -> HashMap<usize, usize> {
-    let map = parse(data);
-    let base_time = shortest_path_length(&map);
+fn cheat_counts_naive(map: &Map) -> HashMap<usize, usize> {
+    let base_time = shortest_path_length(map);
 
-    cheats(&map)
+    cheats(map)
         .iter()
-        .map(|(p, direction)| base_time - shortest_path_length_with_cheat(&map, p, *direction))
+        .map(|(p, direction)| base_time - shortest_path_length_with_cheat(map, p, *direction))
         .filter(|&saving| saving > 0)
         .counts()
 }
 
-fn part1_naive(data: &str) -> usize {
-    cheat_counts_naive(data)
+// Only used for comparison in the tests and benchmarks
+#[allow(dead_code)]
+fn part1_naive(map: &Map) -> usize {
+    cheat_counts_naive(map)
         .into_iter()
-        .filter(|&(saving, c)| saving >= 100)
+        .filter(|&(saving, _)| saving >= 100)
         .map(|(_, count)| count)
         .sum()
 }
 
-// Return a vector where
-// - the index of each item is a number of steps from the position 'pos',
-// - the item at this index is the set of points which can be reached from 'pos' with this number of steps.
-fn points_at_distance(pos: &PointSet, track: &PointSet) -> Vec<PointSet> {
-    successors(Some((pos.clone(), pos.clone())), |(current, visited)| {
-        let new = &current.shake() & &track;
-
-        Some((&new & &!visited, &new | visited))
-    })
-    .map(|(new, _)| new)
-    .take_while(|new| !new.is_empty())
-    .collect()
-}
-
-fn cheat_counts(data: &str) -> HashMap<usize, usize> {
-    let map = parse(data);
-    let base_time = shortest_path_length(&map);
+fn cheat_counts(map: &Map) -> HashMap<usize, usize> {
+    let base_time = shortest_path_length(map);
 
     // Index is distance from start
-    let points_from_start = points_at_distance(&map.start, &map.track);
+    let points_from_start = map.start.distance_layers(&map.track).collect_vec();
 
     // Map each point on the track to its distance to the end
-    let distance_to_end: HashMap<Point, usize> = points_at_distance(&map.end, &map.track)
-        .iter()
+    let distance_to_end: HashMap<Point, usize> = map.end.distance_layers(&map.track)
         .enumerate()
         .filter(|(distance, _)|
             // Do not look at points which are too far away from the best path.
@@ -150,10 +132,7 @@ fn cheat_counts(data: &str) -> HashMap<usize, usize> {
             // closer to the start then the end.
             *distance < base_time - 3
         )
-        .flat_map(|(distance, points)| {
-            println!("distance={}", distance);
-            points.points().into_iter().map(move |p| (p, distance))
-        })
+        .flat_map(|(distance, points)| points.points().into_iter().map(move |p| (p, distance)))
         .collect();
 
     points_from_start
@@ -192,15 +171,15 @@ fn cheat_counts(data: &str) -> HashMap<usize, usize> {
         .counts()
 }
 
-fn part1(data: &str) -> usize {
-    cheat_counts(data)
+fn part1(map: &Map) -> usize {
+    cheat_counts(map)
         .into_iter()
-        .filter(|&(saving, c)| saving >= 100)
+        .filter(|&(saving, _)| saving >= 100)
         .map(|(_, count)| count)
         .sum()
 }
 
-fn part2(data: &str) -> usize {
+fn part2(_map: &Map) -> usize {
     0
 }
 
@@ -283,24 +262,24 @@ mod tests {
 
         assert_eq!(
             expected_cheat_counts,
-            cheat_counts_naive(TEST_INPUT)
+            cheat_counts_naive(&parse(TEST_INPUT))
         );
 
         assert_eq!(
             expected_cheat_counts,
-            cheat_counts(TEST_INPUT)
+            cheat_counts(&parse(TEST_INPUT))
         );
     }
 
     #[test]
     fn test_part1() {
         // actually a boring test - the test map does not have cheats with savings >= 100
-        assert_eq!(0, part1_naive(TEST_INPUT));
-        assert_eq!(0, part1(TEST_INPUT));
+        assert_eq!(0, part1_naive(&parse(TEST_INPUT)));
+        assert_eq!(0, part1(&parse(TEST_INPUT)));
     }
 
     #[test]
     fn test_part2() {
-        //assert_eq!(42, part2(TEST_INPUT));
+        //assert_eq!(42, part2(&parse(TEST_INPUT)));
     }
 }
@@ -1,57 +1,39 @@
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use std::iter::successors;
+use std::collections::HashMap;
+use utils::{Direction, Point, PointSet, PointSets};
 
 fn input() -> String {
-    std::fs::read_to_string("input/day20.txt").expect("Could not read file")
+    aoc_core::input::read(2024, 20)
 }
 
 fn main() {
-    let data = input();
+    let map = parse(&input());
 
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+    println!("Part 1: {}", part1(&map));
+    println!("Part 2: {}", part2(&map));
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
+fn neighbors(p: Point) -> impl Iterator<Item = Point> {
+    Direction::ORTHOGONAL.into_iter().filter_map(move |direction| p.checked_add(direction))
 }
 
-impl Point {
-    fn neighbors(&self) -> Vec<Point> {
-        let Point { x, y } = *self;
-
-        vec![Point { x, y: y - 1 },
-             Point { x, y: y + 1 },
-             Point { x: x - 1, y },
-             Point { x: x + 1, y }]
-    }
-}
-
-struct Map {
-    walls: HashSet<Point>,
-    track: HashSet<Point>,
+pub struct Map {
+    walls: PointSet,
+    track: PointSet,
     start: Point,
     end: Point,
 }
 
 fn parse(data: &str) -> Map {
-    let points_by_char = data.lines().enumerate()
-        .flat_map(|(y, line)|
-            line.chars().enumerate()
-                .map(move |(x, c)| (c, Point { x: x as i32, y: y as i32 }))
-        )
-        .into_group_map();
+    let mut point_sets =
+        PointSets::parse_with_alphabet(data, "#.SE").unwrap_or_else(|error| panic!("{}", error));
 
-    let start = *points_by_char.get(&'S').unwrap().iter().next().unwrap();
-    let end = *points_by_char.get(&'E').unwrap().iter().next().unwrap();
-    let walls: HashSet<Point> = points_by_char.get(&'#').unwrap().iter().copied().collect();
+    let single_point = |c| point_sets.single_point(c).unwrap_or_else(|error| panic!("{}", error));
+    let start = single_point('S');
+    let end = single_point('E');
 
-    let mut track: HashSet<Point> = points_by_char.get(&'.').unwrap().iter().copied().collect();
-    track.insert(start);
-    track.insert(end);
+    let walls = point_sets.remove('#');
+    let track = !&walls;
 
     Map {
         walls,
@@ -62,62 +44,36 @@ fn parse(data: &str) -> Map {
 }
 
 fn shortest_path_length(map: &Map) -> usize {
-    let start: HashSet<Point> = HashSet::from([map.start]);
-
-    let mut visited = start.clone();
-
-    successors(Some(start), |current| {
-        let new: HashSet<Point> = current.iter()
-            .flat_map(|p| p.neighbors())
-            .into_iter()
-            .filter(|p| !map.walls.contains(p))
-            .filter(|p| !visited.contains(p))
-            .collect();
-
-        if (!new.contains(&map.end)) {
-            visited.extend(new.iter());
-            Some(new)
-        } else {
-            None
-        }
-    })
-        .count()
+    let dimensions = &map.walls.dimensions;
+
+    PointSet::from_point(dimensions, &map.start)
+        .distance_to(&PointSet::from_point(dimensions, &map.end), &map.track)
+        .unwrap()
 }
 
 // Return a vector where
 // - the index of each item is a number of steps from the position 'pos',
 // - the item at this index is the set of points which can be reached from 'pos' with this number of steps.
-fn points_at_distance(pos: Point, map: &Map) -> Vec<HashSet<Point>> {
-    successors(
-        Some((HashSet::from([pos]), HashSet::from([pos]))),
-        |(current, visited)| {
-            let new: HashSet<Point> = current.iter()
-                .flat_map(|p| p.neighbors())
-                .filter(|p| map.track.contains(p))
-                .filter(|p| !visited.contains(p))
-                .collect();
-
-            Some((new.clone(), new.union(visited).copied().collect()))
-        })
-        .map(|(new, _)| new)
-        .take_while(|new| !new.is_empty())
+fn points_at_distance(pos: Point, map: &Map) -> Vec<PointSet> {
+    PointSet::from_point(&map.walls.dimensions, &pos)
+        .distance_layers(&map.track)
         .collect()
 }
 
 fn cheat_counts(map: &Map) -> HashMap<usize, usize> {
-    let base_time = shortest_path_length(&map);
+    let base_time = shortest_path_length(map);
 
     // Note that we do not look at points which are too far away from the best path.
 
     // Index is distance from start
-    let points_from_start = points_at_distance(map.start, &map);
+    let points_from_start = points_at_distance(map.start, map);
 
     // Map each point on the track to its distance to the end
-    let distance_to_end: HashMap<Point, usize> = points_at_distance(map.end, &map)
+    let distance_to_end: HashMap<Point, usize> = points_at_distance(map.end, map)
         .into_iter().enumerate()
         .filter(|(distance, _)| *distance <= base_time - 2)
         .flat_map(|(distance, points)|
-            points.into_iter().map(move |p| (p, distance))
+            points.points().into_iter().map(move |p| (p, distance))
         )
         .collect();
 
@@ -133,8 +89,8 @@ fn cheat_counts(map: &Map) -> HashMap<usize, usize> {
         .rev()
         .filter(|(distance, _)| *distance <= base_time - 2)
         .flat_map(|(distance, points)| {
-            points.into_iter().flat_map(move |p|
-                p.neighbors().into_iter()
+            points.points().into_iter().flat_map(move |p|
+                neighbors(p)
                     .filter(|n| map.walls.contains(n))
                     .map(move |n| (n, distance + 1))
             )
@@ -143,7 +99,7 @@ fn cheat_counts(map: &Map) -> HashMap<usize, usize> {
 
     wall_distance_from_start.into_iter()
         .flat_map(|(wall, distance_from_start)|
-            wall.neighbors().into_iter()
+            neighbors(wall)
                 .flat_map(move |n|
                     distance_to_end_ref.get(&n)
                         .map(|to_end| distance_from_start + 1 + *to_end))
@@ -155,15 +111,14 @@ fn cheat_counts(map: &Map) -> HashMap<usize, usize> {
 }
 
 
-fn part1(data: &str) -> usize {
-    let map = parse(data);
-    cheat_counts(&map).into_iter()
-        .filter(|&(saving, c)| saving >= 100)
+fn part1(map: &Map) -> usize {
+    cheat_counts(map).into_iter()
+        .filter(|&(saving, _)| saving >= 100)
         .map(|(_, c)| c)
         .sum()
 }
 
-fn part2(data: &str) -> usize {
+fn part2(_map: &Map) -> usize {
     0
 }
 
@@ -219,11 +174,11 @@ mod tests {
     #[test]
     fn test_part1() {
         // actually a boring test - the test map does not have cheats with savings >= 100
-        assert_eq!(0, part1(TEST_INPUT));
+        assert_eq!(0, part1(&parse(TEST_INPUT)));
     }
 
     #[test]
     fn test_part2() {
-        //assert_eq!(42, part2(TEST_INPUT));
+        //assert_eq!(42, part2(&parse(TEST_INPUT)));
     }
 }
@@ -2,8 +2,7 @@ use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day22.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 22)
 }
 
 fn main() {
@@ -3,8 +3,7 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("input/day23.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, 23)
 }
 
 fn main() {
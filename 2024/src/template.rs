@@ -1,6 +1,5 @@
 fn input() -> String {
-    std::fs::read_to_string("input/dayXX.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2024, XX)
 }
 
 fn main() {
@@ -0,0 +1,213 @@
+// Compares PointSet with a simple model based on HashSet<Point> for random sequences of operations
+// on random grids.
+
+use proptest::prelude::*;
+use std::collections::HashSet;
+use utils::{Dimensions, Direction, Point, PointSet, Topology};
+
+#[derive(Debug, Clone)]
+enum Operation {
+    And(Vec<Point>),
+    Or(Vec<Point>),
+    Xor(Vec<Point>),
+    Not,
+    Shift(Direction),
+    Shake,
+    Shake8,
+    Translate(isize, isize),
+}
+
+fn offset(direction: Direction) -> (isize, isize) {
+    match direction {
+        Direction::Left => (-1, 0),
+        Direction::Right => (1, 0),
+        Direction::Up => (0, -1),
+        Direction::Down => (0, 1),
+        Direction::UpLeft => (-1, -1),
+        Direction::UpRight => (1, -1),
+        Direction::DownLeft => (-1, 1),
+        Direction::DownRight => (1, 1),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    dimensions: Dimensions,
+    topology: Topology,
+    points: HashSet<Point>,
+}
+
+impl Model {
+    fn all_points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.dimensions.height)
+            .flat_map(move |y| (0..self.dimensions.width).map(move |x| Point { x, y }))
+    }
+
+    // The point moved by (dx, dy), or None if it leaves a bounded grid
+    fn translate(&self, point: &Point, dx: isize, dy: isize) -> Option<Point> {
+        let Dimensions { width, height } = self.dimensions;
+
+        match self.topology {
+            Topology::Bounded => point
+                .checked_translate(dx, dy)
+                .filter(|p| self.dimensions.contains(p)),
+            Topology::Toroidal => Some(Point {
+                x: (point.x as isize + dx).rem_euclid(width as isize) as usize,
+                y: (point.y as isize + dy).rem_euclid(height as isize) as usize,
+            }),
+        }
+    }
+
+    fn translate_all(&self, offsets: &[(isize, isize)]) -> HashSet<Point> {
+        self.points
+            .iter()
+            .flat_map(|p| {
+                offsets
+                    .iter()
+                    .filter_map(|(dx, dy)| self.translate(p, *dx, *dy))
+            })
+            .collect()
+    }
+
+    fn apply(&mut self, operation: &Operation) {
+        let other = |points: &Vec<Point>| points.iter().copied().collect::<HashSet<_>>();
+
+        self.points = match operation {
+            Operation::And(points) => &self.points & &other(points),
+            Operation::Or(points) => &self.points | &other(points),
+            Operation::Xor(points) => &self.points ^ &other(points),
+            Operation::Not => self
+                .all_points()
+                .filter(|p| !self.points.contains(p))
+                .collect(),
+            Operation::Shift(direction) => self.translate_all(&[offset(*direction)]),
+            Operation::Shake => self.translate_all(&[(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)]),
+            Operation::Shake8 => {
+                let offsets = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .collect::<Vec<_>>();
+                self.translate_all(&offsets)
+            }
+            Operation::Translate(dx, dy) => self.translate_all(&[(*dx, *dy)]),
+        };
+    }
+
+    fn sorted_points(&self) -> Vec<Point> {
+        let mut points = self.points.iter().copied().collect::<Vec<_>>();
+        points.sort_by_key(|Point { x, y }| (*y, *x));
+        points
+    }
+}
+
+fn apply(point_set: &PointSet, operation: &Operation) -> PointSet {
+    let other = |points: &Vec<Point>| {
+        PointSet::from_point_refs(&point_set.dimensions, points).with_topology(point_set.topology())
+    };
+
+    match operation {
+        Operation::And(points) => point_set & &other(points),
+        Operation::Or(points) => point_set | &other(points),
+        Operation::Xor(points) => point_set ^ &other(points),
+        Operation::Not => !point_set,
+        Operation::Shift(direction) => point_set.shift(*direction),
+        Operation::Shake => point_set.shake(),
+        Operation::Shake8 => point_set.shake8(),
+        Operation::Translate(dx, dy) => point_set.translate(*dx, *dy),
+    }
+}
+
+// Mostly wide grids with several words per row, and narrow ones which include 1xN grids
+fn dimensions() -> impl Strategy<Value = Dimensions> {
+    prop_oneof![(1..=70usize, 1..=5usize), (1..=3usize, 1..=70usize)]
+        .prop_map(|(width, height)| Dimensions { width, height })
+}
+
+fn points(dimensions: &Dimensions) -> impl Strategy<Value = Vec<Point>> {
+    prop::collection::vec(
+        (0..dimensions.width, 0..dimensions.height).prop_map(|(x, y)| Point { x, y }),
+        0..40,
+    )
+}
+
+fn operation(dimensions: &Dimensions) -> impl Strategy<Value = Operation> {
+    let (width, height) = (dimensions.width as isize, dimensions.height as isize);
+
+    prop_oneof![
+        points(dimensions).prop_map(Operation::And),
+        points(dimensions).prop_map(Operation::Or),
+        points(dimensions).prop_map(Operation::Xor),
+        Just(Operation::Not),
+        prop::sample::select(Direction::ALL.to_vec()).prop_map(Operation::Shift),
+        Just(Operation::Shake),
+        Just(Operation::Shake8),
+        (-2 * width..=2 * width, -2 * height..=2 * height)
+            .prop_map(|(dx, dy)| Operation::Translate(dx, dy)),
+    ]
+}
+
+fn case() -> impl Strategy<Value = (Model, Vec<Operation>)> {
+    (dimensions(), any::<bool>()).prop_flat_map(|(dimensions, toroidal)| {
+        let topology = if toroidal {
+            Topology::Toroidal
+        } else {
+            Topology::Bounded
+        };
+        let operations = prop::collection::vec(operation(&dimensions), 0..20);
+        let model = points(&dimensions).prop_map(move |points| Model {
+            dimensions: dimensions.clone(),
+            topology,
+            points: points.into_iter().collect(),
+        });
+
+        (model, operations)
+    })
+}
+
+fn assert_agrees(point_set: &PointSet, model: &Model) -> Result<(), TestCaseError> {
+    prop_assert_eq!(point_set.points(), model.sorted_points());
+    prop_assert_eq!(point_set.len(), model.points.len());
+    prop_assert_eq!(point_set.is_empty(), model.points.is_empty());
+
+    for point in model.all_points() {
+        prop_assert_eq!(point_set.contains(&point), model.points.contains(&point));
+    }
+
+    // points just beyond the right or bottom edge must not alias points inside the grid
+    let Dimensions { width, height } = model.dimensions;
+    let beyond_right = (0..height + 2).flat_map(|y| [width, width + 1].map(|x| Point { x, y }));
+    let beyond_bottom = (0..width + 2).flat_map(|x| [height, height + 1].map(|y| Point { x, y }));
+    for point in beyond_right.chain(beyond_bottom) {
+        prop_assert!(!point_set.contains(&point), "{:?} is outside the grid", point);
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn operations_agree_with_model((mut model, operations) in case()) {
+        let mut point_set = PointSet::from_points(&model.dimensions, model.points.iter().copied())
+            .with_topology(model.topology);
+        assert_agrees(&point_set, &model)?;
+
+        for operation in &operations {
+            point_set = apply(&point_set, operation);
+            model.apply(operation);
+            assert_agrees(&point_set, &model)?;
+        }
+    }
+
+    #[test]
+    fn points_outside_the_grid_are_rejected(
+        dimensions in dimensions(),
+        dx in 0..3usize,
+        dy in 0..3usize,
+    ) {
+        let inside = Point { x: dimensions.width - 1, y: dimensions.height - 1 };
+        prop_assert_eq!(PointSet::from_point(&dimensions, &inside).len(), 1);
+
+        let outside = Point { x: dimensions.width - 1 + dx, y: dimensions.height + dy };
+        let result = std::panic::catch_unwind(|| PointSet::from_point(&dimensions, &outside));
+        prop_assert!(result.is_err());
+    }
+}
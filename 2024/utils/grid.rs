@@ -0,0 +1,280 @@
+use crate::pointset::{parse_map, Dimensions, Direction, GridParseError, Point, PointSet};
+use std::ops::{Index, IndexMut};
+
+// A dense grid with a value of type T at each point. It uses the same coordinates as PointSet,
+// such that both can be converted into each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub dimensions: Dimensions,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(dimensions: &Dimensions, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..dimensions.height)
+            .flat_map(|y| (0..dimensions.width).map(move |x| Point { x, y }))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            dimensions: dimensions.clone(),
+            cells,
+        }
+    }
+
+    // Parse a map with one character per point. If 'f' returns None for a character, an error
+    // with the position of that character is returned.
+    pub fn parse(
+        lines: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridParseError> {
+        let mut cells = Vec::with_capacity(lines.len());
+        let dimensions = parse_map(lines, |_, _, c| f(c).map(|value| cells.push(value)).is_some())?;
+
+        Ok(Self { dimensions, cells })
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, point: &Point) -> Option<usize> {
+        if self.dimensions.contains(point) {
+            Some(point.bit_index(&self.dimensions))
+        } else {
+            None
+        }
+    }
+
+    // The neighbor in the given direction, or None if it would be outside the grid
+    pub fn neighbor(&self, point: &Point, direction: Direction) -> Option<(Point, &T)> {
+        let neighbor = point.checked_add(direction)?;
+        self.get(&neighbor).map(|value| (neighbor, value))
+    }
+
+    // The orthogonal neighbors which are inside the grid
+    pub fn neighbors(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let point = *point;
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.neighbor(&point, direction))
+    }
+
+    // The orthogonal and diagonal neighbors which are inside the grid
+    pub fn neighbors8(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let point = *point;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.neighbor(&point, direction))
+    }
+
+    // All points with their values in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (Point::from_bit_index(i, &self.dimensions), value))
+    }
+
+    // A grid with width 0 has no cells, so there are no rows either
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.dimensions.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.dimensions.width.max(1))
+            .take(if x < self.dimensions.width {
+                self.dimensions.height
+            } else {
+                0
+            })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.dimensions.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            dimensions: self.dimensions.clone(),
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // All points whose value satisfies the predicate, e.g., all cells with height 9
+    pub fn to_point_set(&self, predicate: impl Fn(&T) -> bool) -> PointSet {
+        PointSet::from_points(
+            &self.dimensions,
+            self.iter()
+                .filter(|(_, value)| predicate(value))
+                .map(|(point, _)| point),
+        )
+    }
+
+    // Map each point to a value, depending on whether it is in the given set or not
+    pub fn from_point_set(point_set: &PointSet, mut f: impl FnMut(bool) -> T) -> Self {
+        Self::from_fn(&point_set.dimensions, |point| f(point_set.contains(&point)))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(dimensions: &Dimensions, value: T) -> Self {
+        Self {
+            dimensions: dimensions.clone(),
+            cells: vec![value; dimensions.width * dimensions.height],
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(&point).expect("Point out of bounds")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(&point).expect("Point out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    const HEIGHTS: &str = "0123\n1234\n8765\n9876";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(HEIGHTS, |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(
+            grid.dimensions,
+            Dimensions {
+                width: 4,
+                height: 4
+            }
+        );
+        assert_eq!(grid[Point { x: 3, y: 1 }], 4);
+        assert_eq!(grid.get(&Point { x: 0, y: 3 }), Some(&9));
+        assert_eq!(grid.get(&Point { x: 4, y: 0 }), None);
+        assert_eq!(grid.get(&Point { x: 0, y: 4 }), None);
+
+        assert_eq!(
+            Grid::parse("01\n2x", |c| c.to_digit(10)),
+            Err(GridParseError::UnexpectedCharacter {
+                row: 1,
+                column: 1,
+                character: 'x'
+            })
+        );
+        assert_eq!(
+            Grid::parse("01\n2", |c| c.to_digit(10)),
+            Err(GridParseError::RaggedRow {
+                row: 1,
+                width: 1,
+                expected_width: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse("", |c| c.to_digit(10)),
+            Err(GridParseError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse(HEIGHTS, |c| c.to_digit(10)).unwrap();
+
+        let corner = Point { x: 0, y: 0 };
+        assert_eq!(grid.neighbor(&corner, Direction::Left), None);
+        assert_eq!(grid.neighbor(&corner, Direction::Up), None);
+        assert_eq!(
+            grid.neighbor(&corner, Direction::Right),
+            Some((Point { x: 1, y: 0 }, &1))
+        );
+        assert_eq!(grid.neighbors(&corner).count(), 2);
+        assert_eq!(grid.neighbors8(&corner).count(), 3);
+
+        let center = Point { x: 1, y: 2 };
+        assert_eq!(
+            grid.neighbors(&center)
+                .map(|(_, h)| *h)
+                .sorted()
+                .collect_vec(),
+            vec![2, 6, 8, 8]
+        );
+        assert_eq!(grid.neighbors8(&center).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse(HEIGHTS, |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid.rows().nth(2), Some(&[8, 7, 6, 5][..]));
+        assert_eq!(grid.column(1).copied().collect_vec(), vec![1, 2, 7, 8]);
+        assert_eq!(grid.column(4).count(), 0);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.sum::<u32>())
+                .collect_vec(),
+            vec![18, 18, 18, 18]
+        );
+        assert_eq!(grid.iter().nth(5), Some((Point { x: 1, y: 1 }, &2)));
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let grid = Grid::new(
+            &Dimensions {
+                width: 0,
+                height: 3,
+            },
+            0,
+        );
+
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.column(0).count(), 0);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_point_set_conversion() {
+        let mut grid = Grid::parse(HEIGHTS, |c| c.to_digit(10)).unwrap();
+        grid[Point { x: 2, y: 2 }] = 9;
+
+        let nines = grid.to_point_set(|h| *h == 9);
+        assert_eq!(
+            nines.points(),
+            vec![Point { x: 2, y: 2 }, Point { x: 0, y: 3 }]
+        );
+
+        let back = Grid::from_point_set(&nines, |inside| if inside { '#' } else { '.' });
+        assert_eq!(
+            back.rows()
+                .map(|row| row.iter().collect::<String>())
+                .join("\n"),
+            "....\n....\n..#.\n#..."
+        );
+        assert_eq!(
+            back.map(|c| *c == '#').to_point_set(|inside| *inside),
+            nines
+        );
+
+        assert_eq!(
+            Grid::new(&grid.dimensions, 0).to_point_set(|h| *h == 0),
+            grid.dimensions.full()
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::grid::Grid;
+use crate::pointset::{Dimensions, Point, PointSet};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+// Maps the characters used in point_sets_map to colors. Points which are in none of the sets get
+// the background color, characters without a color get the default color.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: HashMap<char, Rgb>,
+    background: Rgb,
+    default: Rgb,
+}
+
+impl Palette {
+    pub fn new(background: Rgb, default: Rgb) -> Self {
+        Self {
+            colors: HashMap::new(),
+            background,
+            default,
+        }
+    }
+
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb {
+        *self.colors.get(&c).unwrap_or(&self.default)
+    }
+}
+
+impl Default for Palette {
+    // White points on a black background
+    fn default() -> Self {
+        Self::new([0, 0, 0], [255, 255, 255])
+    }
+}
+
+// An RGB image in which each point of a map is drawn as a square of cell_size x cell_size pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        cell_size: usize,
+        mut color: impl FnMut(&T) -> Rgb,
+    ) -> Self {
+        let Dimensions { width, height } = grid.dimensions;
+        let mut image = Self {
+            width: width * cell_size,
+            height: height * cell_size,
+            pixels: vec![[0, 0, 0]; width * height * cell_size * cell_size],
+        };
+
+        for (Point { x, y }, value) in grid.iter() {
+            let color = color(value);
+            for row in y * cell_size..(y + 1) * cell_size {
+                let start = row * image.width + x * cell_size;
+                image.pixels[start..start + cell_size].fill(color);
+            }
+        }
+
+        image
+    }
+
+    // Uses the same layering as point_sets_map: if a point is in several sets, the first one wins
+    pub fn from_point_sets(
+        sets: &[(char, &PointSet)],
+        palette: &Palette,
+        cell_size: usize,
+    ) -> Self {
+        let Some((_, first)) = sets.first() else {
+            return Self {
+                width: 0,
+                height: 0,
+                pixels: Vec::new(),
+            };
+        };
+
+        let grid = Grid::from_fn(&first.dimensions, |point| {
+            sets.iter()
+                .find(|(_, points)| points.contains(&point))
+                .map_or(palette.background, |(c, _)| palette.color(*c))
+        });
+
+        Self::from_grid(&grid, cell_size, |color| *color)
+    }
+
+    // Renders the output of point_sets_map, where spaces are points which are in none of the sets
+    pub fn from_map(map: &str, palette: &Palette, cell_size: usize) -> Self {
+        let rows = map
+            .lines()
+            .map(|row| row.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let dimensions = Dimensions {
+            width: rows.iter().map(|row| row.len()).max().unwrap_or(0),
+            height: rows.len(),
+        };
+
+        let grid = Grid::from_fn(&dimensions, |Point { x, y }| match rows[y].get(x) {
+            None | Some(' ') => palette.background,
+            Some(c) => palette.color(*c),
+        });
+
+        Self::from_grid(&grid, cell_size, |color| *color)
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    // All pixels in row-major order
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    // Binary PPM (P6), which most image viewers can open
+    pub fn write_ppm(&self, mut writer: impl Write) -> std::io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(self.pixels.as_flattened())
+    }
+
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, writer: impl Write) -> std::io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels.as_flattened())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    // Writes a PNG if the file name ends with ".png" (requires the "png" feature), and a PPM
+    // otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let writer = BufWriter::new(File::create(path)?);
+
+        if path.extension().is_some_and(|extension| extension == "png") {
+            #[cfg(feature = "png")]
+            return self.write_png(writer);
+
+            #[cfg(not(feature = "png"))]
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "PNG export requires the \"png\" feature",
+            ));
+        }
+
+        self.write_ppm(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointset::point_sets_map;
+
+    #[test]
+    fn test_point_sets() {
+        let dimensions = Dimensions {
+            width: 3,
+            height: 2,
+        };
+        let walls =
+            PointSet::from_points(&dimensions, [Point { x: 0, y: 0 }, Point { x: 1, y: 0 }]);
+        let robot = PointSet::from_point(&dimensions, &Point { x: 1, y: 0 });
+        let boxes = PointSet::from_point(&dimensions, &Point { x: 2, y: 1 });
+
+        let sets = vec![('@', &robot), ('#', &walls), ('O', &boxes)];
+        assert_eq!(point_sets_map(&sets), "#@ \n  O");
+
+        let palette = Palette::default()
+            .with('#', [128, 128, 128])
+            .with('@', [255, 0, 0]);
+        let image = Image::from_point_sets(&sets, &palette, 2);
+
+        assert_eq!((image.width, image.height), (6, 4));
+        assert_eq!(image.pixel(0, 0), [128, 128, 128]);
+        assert_eq!(image.pixel(3, 1), [255, 0, 0]);
+        assert_eq!(image.pixel(5, 1), [0, 0, 0]);
+        assert_eq!(image.pixel(4, 3), [255, 255, 255]);
+
+        assert_eq!(Image::from_map(&point_sets_map(&sets), &palette, 2), image);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let grid = Grid::parse("01\n10", |c| c.to_digit(10)).unwrap();
+        let image = Image::from_grid(&grid, 1, |&v| [v as u8 * 255, 0, 0]);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([0, 0, 0, 255, 0, 0, 255, 0, 0, 0, 0, 0]);
+        assert_eq!(ppm, expected);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_write_png() {
+        let grid = Grid::parse("01\n10", |c| c.to_digit(10)).unwrap();
+        let image = Image::from_grid(&grid, 3, |&v| [v as u8 * 255, 0, 0]);
+
+        let mut data = Vec::new();
+        image.write_png(&mut data).unwrap();
+
+        let mut reader = png::Decoder::new(data.as_slice()).read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        assert_eq!(&buffer[9..12], &[255, 0, 0]);
+    }
+}
@@ -1 +1,15 @@
-mod pointset;
\ No newline at end of file
+pub mod grid;
+pub mod image;
+pub mod pattern;
+pub mod pointset;
+pub mod recorder;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod sparse;
+
+pub use grid::Grid;
+pub use image::{Image, Palette, Rgb};
+pub use pattern::Pattern;
+pub use pointset::*;
+pub use recorder::{Frame, Recorder};
+pub use sparse::{IPoint, SparsePointSet};
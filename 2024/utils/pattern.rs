@@ -0,0 +1,284 @@
+use crate::grid::Grid;
+use crate::pointset::{Dimensions, PointSet};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Cell {
+    dx: isize,
+    dy: isize,
+    layer: char,
+    required: bool,
+}
+
+// A small template of cells relative to an anchor point. Each cell requires or forbids a point in
+// one of several layers, e.g., the positions of the letters in a word search. Points outside the
+// grid are not in any layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pattern {
+    cells: Vec<Cell>,
+}
+
+impl Pattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Each character of the template requires a point in the layer of that character, except for
+    // '?', which matches anything. The anchor is the top left corner of the template.
+    pub fn parse(template: &str) -> Self {
+        template
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| row.chars().enumerate().map(move |(x, c)| (x, y, c)))
+            .filter(|(_, _, c)| *c != '?')
+            .fold(Self::new(), |pattern, (x, y, c)| {
+                pattern.require(c, x as isize, y as isize)
+            })
+    }
+
+    // The point at offset (dx, dy) from the anchor must be in the given layer
+    pub fn require(self, layer: char, dx: isize, dy: isize) -> Self {
+        self.with_cell(Cell {
+            dx,
+            dy,
+            layer,
+            required: true,
+        })
+    }
+
+    // The point at offset (dx, dy) from the anchor must not be in the given layer
+    pub fn forbid(self, layer: char, dx: isize, dy: isize) -> Self {
+        self.with_cell(Cell {
+            dx,
+            dy,
+            layer,
+            required: false,
+        })
+    }
+
+    fn with_cell(mut self, cell: Cell) -> Self {
+        self.cells.push(cell);
+        self
+    }
+
+    fn map_cells(&self, f: impl Fn(isize, isize) -> (isize, isize)) -> Self {
+        Self {
+            cells: self
+                .cells
+                .iter()
+                .map(|cell| {
+                    let (dx, dy) = f(cell.dx, cell.dy);
+                    Cell { dx, dy, ..*cell }
+                })
+                .collect(),
+        }
+    }
+
+    // The pattern rotated by 90 degrees clockwise around the anchor
+    pub fn rotate90(&self) -> Self {
+        self.map_cells(|dx, dy| (-dy, dx))
+    }
+
+    // The pattern mirrored at the vertical line through the anchor
+    pub fn flip_horizontal(&self) -> Self {
+        self.map_cells(|dx, dy| (-dx, dy))
+    }
+
+    // All distinct patterns which can be obtained by rotations and reflections. Patterns which
+    // differ only in the position of the anchor are considered equal, such that each occurrence
+    // in a grid is matched by only one of them.
+    pub fn orientations(&self) -> Vec<Self> {
+        let rotations = |pattern: Self| {
+            (0..4).scan(pattern, |pattern, _| {
+                let current = pattern.clone();
+                *pattern = pattern.rotate90();
+                Some(current)
+            })
+        };
+
+        rotations(self.clone())
+            .chain(rotations(self.flip_horizontal()))
+            .unique_by(|pattern| pattern.normalized_cells())
+            .collect()
+    }
+
+    // The cells moved such that the smallest offsets are zero, in a canonical order
+    fn normalized_cells(&self) -> Vec<Cell> {
+        let min_dx = self.cells.iter().map(|cell| cell.dx).min().unwrap_or(0);
+        let min_dy = self.cells.iter().map(|cell| cell.dy).min().unwrap_or(0);
+
+        self.map_cells(|dx, dy| (dx - min_dx, dy - min_dy))
+            .cells
+            .into_iter()
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    // All anchor points where the pattern matches. Layers which are not in the list are empty. All
+    // layers must have the same dimensions.
+    pub fn find(&self, layers: &[(char, &PointSet)]) -> PointSet {
+        let (_, first) = layers.first().expect("At least one layer is required");
+        let empty = first.dimensions.empty().with_topology(first.topology());
+
+        self.cells.iter().fold(!&empty, |anchors, cell| {
+            let layer = layers
+                .iter()
+                .find(|(c, _)| *c == cell.layer)
+                .map_or(&empty, |(_, layer)| *layer);
+
+            // the anchors which have the layer's point at the offset of the cell
+            let matching = layer.translate(-cell.dx, -cell.dy);
+            if cell.required {
+                anchors & matching
+            } else {
+                anchors & !matching
+            }
+        })
+    }
+
+    // The same as find, but with one layer for each character of the grid
+    pub fn find_in_grid(&self, grid: &Grid<char>) -> PointSet {
+        let layers = self
+            .cells
+            .iter()
+            .map(|cell| cell.layer)
+            .unique()
+            .map(|c| (c, grid.to_point_set(|value| *value == c)))
+            .collect_vec();
+
+        if layers.is_empty() {
+            return grid.dimensions.full();
+        }
+
+        self.find(&layers.iter().map(|(c, layer)| (*c, layer)).collect_vec())
+    }
+
+    // The number of matches of all orientations of the pattern
+    pub fn count_all_orientations(&self, layers: &[(char, &PointSet)]) -> usize {
+        self.orientations()
+            .iter()
+            .map(|pattern| pattern.find(layers).len())
+            .sum()
+    }
+
+    // The size of the smallest rectangle which contains all cells
+    pub fn dimensions(&self) -> Dimensions {
+        let extent = |values: Vec<isize>| match values.iter().minmax().into_option() {
+            Some((min, max)) => (max - min + 1) as usize,
+            None => 0,
+        };
+
+        Dimensions {
+            width: extent(self.cells.iter().map(|cell| cell.dx).collect()),
+            height: extent(self.cells.iter().map(|cell| cell.dy).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointset::{Point, PointSets};
+
+    const WORD_SEARCH: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_word_search() {
+        let point_sets = PointSets::parse(WORD_SEARCH).unwrap();
+        let letters = point_sets
+            .chars()
+            .map(|c| (c, point_sets.get(c)))
+            .collect_vec();
+        let layers = letters.iter().map(|(c, layer)| (*c, layer)).collect_vec();
+
+        // 2024 day 4, part 1: XMAS in all 8 directions. Reflections of a straight word are also
+        // rotations, so each of the two patterns has only four orientations.
+        let straight = Pattern::parse("XMAS");
+        let diagonal = Pattern::parse("X???\n?M??\n??A?\n???S");
+        assert_eq!(straight.orientations().len(), 4);
+        assert_eq!(diagonal.orientations().len(), 4);
+        assert_eq!(
+            straight.count_all_orientations(&layers) + diagonal.count_all_orientations(&layers),
+            18
+        );
+
+        // part 2: two MAS in the shape of an X
+        let x_mas = Pattern::parse("M?S\n?A?\nM?S");
+        assert_eq!(x_mas.orientations().len(), 4);
+        assert_eq!(x_mas.count_all_orientations(&layers), 9);
+
+        // the same search in a grid of characters
+        let grid = Grid::parse(WORD_SEARCH, Some).unwrap();
+        assert_eq!(
+            straight.find_in_grid(&grid).points(),
+            vec![
+                Point { x: 5, y: 0 },
+                Point { x: 0, y: 4 },
+                Point { x: 5, y: 9 }
+            ]
+        );
+        assert_eq!(straight.find_in_grid(&grid), straight.find(&layers));
+    }
+
+    #[test]
+    fn test_forbidden_cells() {
+        let point_sets = PointSets::parse_with_alphabet("#..#\n#.##\n....", "#.").unwrap();
+        let walls = point_sets.get('#');
+
+        // walls whose right neighbor is free, including the grid edge
+        let pattern = Pattern::new().require('#', 0, 0).forbid('#', 1, 0);
+        assert_eq!(
+            pattern.find(&[('#', &walls)]).points(),
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 3, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 3, y: 1 }
+            ]
+        );
+
+        // missing layers are empty
+        assert_eq!(pattern.find(&[('.', &!&walls)]), walls.dimensions.empty());
+        assert_eq!(
+            Pattern::new().forbid('O', 0, 0).find(&[('#', &walls)]),
+            walls.dimensions.full()
+        );
+
+        // offsets which point to the left of the anchor
+        let below_left = Pattern::new().require('#', 0, 0).require('#', -1, 1);
+        assert_eq!(
+            below_left.find(&[('#', &walls)]).points(),
+            vec![Point { x: 3, y: 0 }]
+        );
+        assert_eq!(
+            below_left.dimensions(),
+            Dimensions {
+                width: 2,
+                height: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        let corner = Pattern::parse("##\n#?");
+        assert_eq!(corner.orientations().len(), 4);
+
+        let square = Pattern::parse("##\n##");
+        assert_eq!(square.orientations().len(), 1);
+
+        let l_shape = Pattern::parse("#?\n#?\n##");
+        assert_eq!(l_shape.orientations().len(), 8);
+        assert_eq!(l_shape.rotate90().rotate90().rotate90().rotate90(), l_shape);
+    }
+}
@@ -1,32 +1,231 @@
+use crate::grid::Grid;
 use itertools::Itertools;
 use num_bigint::BigUint;
-use std::collections::{HashMap, HashSet};
-use std::fmt::Debug;
-use std::ops::{Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
-
-#[derive(Debug, Eq, PartialEq, Clone)]
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{
+    Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Range,
+};
+use std::sync::{Arc, OnceLock, Weak};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dimensions {
     pub width: usize,
     pub height: usize,
 }
 
+// Bit masks which are needed again and again for a given grid size. They are shared by all point
+// sets with the same dimensions.
+struct Masks {
+    dimensions: Dimensions,
+
+    // all points of the grid
+    full: Vec<u64>,
+
+    // left[n] contains the points in the columns 0..n, and right[n] those in the columns
+    // width - n..width. They are only computed for the shifts which need them.
+    left: Vec<OnceLock<Vec<u64>>>,
+    right: Vec<OnceLock<Vec<u64>>>,
+}
+
+impl Masks {
+    fn new(dimensions: &Dimensions) -> Self {
+        let columns = || (0..=dimensions.width).map(|_| OnceLock::new()).collect();
+
+        Masks {
+            dimensions: dimensions.clone(),
+            full: full_words(dimensions),
+            left: columns(),
+            right: columns(),
+        }
+    }
+
+    fn left_columns(&self, n: usize) -> &[u64] {
+        self.left[n].get_or_init(|| columns_mask(&self.dimensions, 0..n))
+    }
+
+    fn right_columns(&self, n: usize) -> &[u64] {
+        let width = self.dimensions.width;
+        self.right[n].get_or_init(|| columns_mask(&self.dimensions, width - n..width))
+    }
+
+    // The masks for the dimensions. Each thread keeps the masks of all grid sizes which are still
+    // used by one of its point sets, so a lookup is a hash map access without locking.
+    fn shared(dimensions: &Dimensions) -> Arc<Masks> {
+        thread_local! {
+            static CACHE: RefCell<HashMap<Dimensions, Weak<Masks>>> = RefCell::new(HashMap::new());
+        }
+
+        CACHE.with_borrow_mut(|cache| {
+            if let Some(masks) = cache.get(dimensions).and_then(Weak::upgrade) {
+                return masks;
+            }
+
+            // forget the grid sizes which are not used any more
+            cache.retain(|_, masks| masks.strong_count() > 0);
+
+            let masks = Arc::new(Masks::new(dimensions));
+            cache.insert(dimensions.clone(), Arc::downgrade(&masks));
+            masks
+        })
+    }
+}
+
 impl Dimensions {
-    pub fn empty(&self) -> PointSet {
+    fn word_count(&self) -> usize {
+        (self.width * self.height).div_ceil(WORD_BITS)
+    }
+
+    fn point_set(&self, words: Vec<u64>) -> PointSet {
         PointSet {
             dimensions: self.clone(),
-            data: BigUint::ZERO,
+            topology: Topology::Bounded,
+            masks: Masks::shared(self),
+            words,
         }
     }
 
+    pub fn empty(&self) -> PointSet {
+        self.point_set(vec![0; self.word_count()])
+    }
+
     pub fn full(&self) -> PointSet {
-        PointSet {
-            dimensions: self.clone(),
-            data: (BigUint::from(1u32) << self.width * self.height) - BigUint::from(1u32),
+        self.point_set(full_words(self))
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    // All points with the given x coordinate
+    pub fn column(&self, x: usize) -> PointSet {
+        PointSet::from_points(self, (0..self.height).map(|y| Point { x, y }))
+    }
+}
+
+// All points of the grid
+fn full_words(dimensions: &Dimensions) -> Vec<u64> {
+    let bit_count = dimensions.width * dimensions.height;
+    let mut words = vec![u64::MAX; dimensions.word_count()];
+    if !bit_count.is_multiple_of(WORD_BITS) {
+        *words.last_mut().unwrap() = (1 << (bit_count % WORD_BITS)) - 1;
+    }
+    words
+}
+
+fn low_bits(len: usize) -> u64 {
+    if len == WORD_BITS {
+        u64::MAX
+    } else {
+        (1 << len) - 1
+    }
+}
+
+// The len <= 64 bits which start at bit index 'start'
+fn get_bits(words: &[u64], start: usize, len: usize) -> u64 {
+    let (i, offset) = (start / WORD_BITS, start % WORD_BITS);
+
+    let mut bits = words[i] >> offset;
+    if offset + len > WORD_BITS {
+        bits |= words[i + 1] << (WORD_BITS - offset);
+    }
+
+    bits & low_bits(len)
+}
+
+// Set the bits which are set in the lowest len <= 64 bits of 'bits', starting at bit index 'start'
+fn or_bits(words: &mut [u64], start: usize, len: usize, bits: u64) {
+    let (i, offset) = (start / WORD_BITS, start % WORD_BITS);
+    let bits = bits & low_bits(len);
+
+    words[i] |= bits << offset;
+    if offset + len > WORD_BITS {
+        words[i + 1] |= bits >> (WORD_BITS - offset);
+    }
+}
+
+// Copy a rectangle with the given size from 'source' at 'from' to 'target' at 'to'
+fn copy_rectangle(
+    source: &PointSet,
+    from: Point,
+    target: &mut PointSet,
+    to: Point,
+    width: usize,
+    height: usize,
+) {
+    for y in 0..height {
+        let source_start = Point { x: from.x, y: from.y + y }.bit_index(&source.dimensions);
+        let target_start = Point { x: to.x, y: to.y + y }.bit_index(&target.dimensions);
+
+        for x in (0..width).step_by(WORD_BITS) {
+            let len = WORD_BITS.min(width - x);
+            let bits = get_bits(&source.words, source_start + x, len);
+            or_bits(&mut target.words, target_start + x, len, bits);
+        }
+    }
+}
+
+// All points in the given range of columns
+fn columns_mask(dimensions: &Dimensions, columns: Range<usize>) -> Vec<u64> {
+    let mut words = vec![0; dimensions.word_count()];
+
+    for y in 0..dimensions.height {
+        for x in columns.clone().step_by(WORD_BITS) {
+            let len = WORD_BITS.min(columns.end - x);
+            or_bits(&mut words, x + y * dimensions.width, len, u64::MAX);
         }
     }
+
+    words
+}
+
+// Move all bits n positions towards the least significant bit. Bits which are shifted out of the
+// first word are dropped.
+fn shift_down_bits(words: &[u64], n: usize) -> Vec<u64> {
+    let (word_offset, bit_offset) = (n / WORD_BITS, n % WORD_BITS);
+    let word = |i: usize| words.get(i).copied().unwrap_or(0);
+
+    (0..words.len())
+        .map(|i| {
+            let low = word(i + word_offset) >> bit_offset;
+            if bit_offset == 0 {
+                low
+            } else {
+                low | word(i + word_offset + 1) << (WORD_BITS - bit_offset)
+            }
+        })
+        .collect()
+}
+
+// Move all bits n positions towards the most significant bit. Bits which are shifted beyond the
+// last word are dropped, but the caller has to clear any bits beyond the end of the grid.
+fn shift_up_bits(words: &[u64], n: usize) -> Vec<u64> {
+    let (word_offset, bit_offset) = (n / WORD_BITS, n % WORD_BITS);
+    let word = |i: usize| {
+        i.checked_sub(word_offset)
+            .and_then(|i| words.get(i))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    (0..words.len())
+        .map(|i| {
+            let high = word(i) << bit_offset;
+            if bit_offset == 0 || i == 0 {
+                high
+            } else {
+                high | word(i - 1) >> (WORD_BITS - bit_offset)
+            }
+        })
+        .collect()
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: usize,
     pub y: usize,
@@ -36,28 +235,114 @@ impl Point {
     pub fn to_big_uint(&self, dimensions: &Dimensions) -> BigUint {
         BigUint::from(1u32) << (self.x + self.y * dimensions.width)
     }
+
+    // The neighbor in the given direction, or None if a coordinate would become negative
+    pub fn checked_add(self, direction: Direction) -> Option<Point> {
+        let Point { x, y } = self;
+
+        let (x, y) = match direction {
+            Direction::Left => (x.checked_sub(1)?, y),
+            Direction::Right => (x + 1, y),
+            Direction::Up => (x, y.checked_sub(1)?),
+            Direction::Down => (x, y + 1),
+            Direction::UpLeft => (x.checked_sub(1)?, y.checked_sub(1)?),
+            Direction::UpRight => (x + 1, y.checked_sub(1)?),
+            Direction::DownLeft => (x.checked_sub(1)?, y + 1),
+            Direction::DownRight => (x + 1, y + 1),
+        };
+
+        Some(Point { x, y })
+    }
+
+    // The point moved by (dx, dy), or None if a coordinate would become negative or overflow
+    pub fn checked_translate(self, dx: isize, dy: isize) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    // Index of the point in row-major order
+    pub(crate) fn bit_index(&self, dimensions: &Dimensions) -> usize {
+        self.x + self.y * dimensions.width
+    }
+
+    pub(crate) fn from_bit_index(n: usize, dimensions: &Dimensions) -> Self {
+        Point {
+            x: n % dimensions.width,
+            y: n / dimensions.width,
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Left,
     Right,
     Up,
     Down,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::DownRight => Direction::UpLeft,
+        }
+    }
 }
 
 impl Add<Direction> for Point {
     type Output = Point;
 
     fn add(self, rhs: Direction) -> Self::Output {
-        let Point{x, y} = self;
+        self.checked_add(rhs)
+            .unwrap_or_else(|| panic!("Cannot move {:?} {:?}", self, rhs))
+    }
+}
 
-        match rhs {
-            Direction::Left => Point { x: x - 1, y: y },
-            Direction::Right => Point { x: x + 1, y: y },
-            Direction::Up => Point { x: x, y: y - 1 },
-            Direction::Down => Point { x: x, y: y + 1 },
-        }
+impl Add<(isize, isize)> for Point {
+    type Output = Point;
+
+    fn add(self, (dx, dy): (isize, isize)) -> Self::Output {
+        self.checked_translate(dx, dy)
+            .unwrap_or_else(|| panic!("Cannot move {:?} by ({}, {})", self, dx, dy))
     }
 }
 
@@ -77,143 +362,859 @@ impl TryFrom<char> for Direction {
     }
 }
 
-#[derive(PartialEq, Eq, Clone)]
+// Determines what happens to points which are shifted beyond the edges of the grid. The result of
+// &, | and ^ has the topology of the left operand, so a toroidal set stays toroidal when it is
+// combined with bounded helpers like Dimensions::full() or Dimensions::column().
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    // points are dropped at the edges
+    Bounded,
+
+    // points which leave the grid on one side enter it again on the opposite side
+    Toroidal,
+}
+
+#[derive(Clone)]
 pub struct PointSet {
     pub dimensions: Dimensions,
-    data: BigUint,
+    topology: Topology,
+    masks: Arc<Masks>,
+    words: Vec<u64>,
 }
 
 impl PointSet {
     pub fn from_point(dimensions: &Dimensions, point: &Point) -> Self {
-        let Point { x, y } = point;
-        if *x >= dimensions.width || *y > dimensions.height {
-            panic!("Point out of bounds");
-        }
-
-        let data = point.to_big_uint(&dimensions);
-        Self {
-            dimensions: dimensions.clone(),
-            data,
-        }
+        Self::from_point_refs(dimensions, [point])
     }
 
     pub fn from_point_refs<'a>(
         dimensions: &Dimensions,
         points: impl IntoIterator<Item = &'a Point>,
     ) -> Self {
-        Self {
-            dimensions: dimensions.clone(),
-            data: points.into_iter().fold(BigUint::ZERO, |acc, point| {
-                let Point { x, y } = point;
-                if *x >= dimensions.width || *y > dimensions.height {
-                    panic!("Point out of bounds");
-                }
-
-                acc | point.to_big_uint(&dimensions)
-            }),
+        let mut result = dimensions.empty();
+        for point in points {
+            result.insert(point);
         }
+        result
     }
 
     pub fn from_points(dimensions: &Dimensions, points: impl IntoIterator<Item = Point>) -> Self {
-        Self {
-            dimensions: dimensions.clone(),
-            data: points.into_iter().fold(BigUint::ZERO, |acc, point| {
-                let Point { x, y } = point;
-                if x >= dimensions.width || y > dimensions.height {
-                    panic!("Point out of bounds");
-                }
+        let mut result = dimensions.empty();
+        for point in points {
+            result.insert(&point);
+        }
+        result
+    }
 
-                acc | point.to_big_uint(&dimensions)
-            }),
+    fn insert(&mut self, point: &Point) {
+        if !self.dimensions.contains(point) {
+            panic!("Point out of bounds");
         }
+
+        let n = point.bit_index(&self.dimensions);
+        self.words[n / WORD_BITS] |= 1 << (n % WORD_BITS);
     }
 
-    /*
-    pub fn points(self: &Self) -> Vec<Point> {
-        (0..self.dimensions.height)
-            .flat_map(
-                |y| (0..self.dimensions.width)
-                    .map(move |x| Point { x, y })
-                    .filter(|p| self.contains(p))
-            )
-            .collect()
+    // Insert the points with the bit indices in the range, whole words at a time
+    #[cfg(feature = "serde")]
+    pub(crate) fn insert_bit_range(&mut self, bits: Range<usize>) {
+        assert!(
+            bits.end <= self.dimensions.width * self.dimensions.height,
+            "Points out of bounds"
+        );
+
+        for start in bits.clone().step_by(WORD_BITS) {
+            let len = WORD_BITS.min(bits.end - start);
+            or_bits(&mut self.words, start, len, u64::MAX);
+        }
+    }
+
+    pub fn points(&self) -> Vec<Point> {
+        self.iter().collect()
+    }
+
+    // Iterate over the points in row-major order
+    pub fn iter(&self) -> Points<'_> {
+        Points {
+            point_set: self,
+            word_index: 0,
+            remaining: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn first(&self) -> Option<Point> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<Point> {
+        self.words
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, word)| **word != 0)
+            .map(|(i, word)| {
+                let n = i * WORD_BITS + (WORD_BITS - 1 - word.leading_zeros() as usize);
+                Point::from_bit_index(n, &self.dimensions)
+            })
     }
-    */
 
-    pub fn points(self: &Self) -> Vec<Point> {
-        (0..self.dimensions.width * self.dimensions.height)
-            .filter(|n| &self.data & BigUint::from(1u32) << n != BigUint::ZERO)
-            .map(|n| (
-                usize::try_from(&n % self.dimensions.width).unwrap(),
-                usize::try_from(n / self.dimensions.width).unwrap()
-            ))
-            .map(|(x, y)| Point{x, y})
-            .collect()
+    // The n-th point in row-major order, counting from zero. Whole words are skipped by their
+    // number of points, such that only a single word has to be examined bit by bit.
+    pub fn nth(&self, mut n: usize) -> Option<Point> {
+        for (i, word) in self.words.iter().enumerate() {
+            let count = word.count_ones() as usize;
+            if n >= count {
+                n -= count;
+                continue;
+            }
+
+            let mut remaining = *word;
+            for _ in 0..n {
+                remaining &= remaining - 1;
+            }
+
+            let bit = i * WORD_BITS + remaining.trailing_zeros() as usize;
+            return Some(Point::from_bit_index(bit, &self.dimensions));
+        }
+
+        None
     }
 
     pub fn is_empty(&self) -> bool {
-        self.data == BigUint::ZERO
+        self.words.iter().all(|word| *word == 0)
     }
 
     pub fn len(&self) -> usize {
-        self.data.count_ones() as usize
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
     }
 
     pub fn contains(&self, point: &Point) -> bool {
-        &self.data & point.to_big_uint(&self.dimensions) != BigUint::ZERO
+        if !self.dimensions.contains(point) {
+            return false;
+        }
+
+        let n = point.bit_index(&self.dimensions);
+        self.words
+            .get(n / WORD_BITS)
+            .is_some_and(|word| word & 1 << (n % WORD_BITS) != 0)
     }
 
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    // The same points, but shift and all operations which are based on it treat the grid with the
+    // given topology
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    fn masks(&self) -> &Masks {
+        &self.masks
+    }
+
+    fn with_words(&self, words: Vec<u64>) -> Self {
+        Self {
+            dimensions: self.dimensions.clone(),
+            topology: self.topology,
+            masks: self.masks.clone(),
+            words,
+        }
+    }
+
+    // The empty set with the same dimensions and topology
+    fn empty_like(&self) -> Self {
+        self.with_words(vec![0; self.words.len()])
+    }
+
+    fn and_words(mut self, mask: &[u64]) -> Self {
+        self.words.iter_mut().zip(mask).for_each(|(word, m)| *word &= m);
+        self
+    }
+
+    // Add the points which have left the grid on the opposite side if the topology is toroidal.
+    // 'wrapped' must contain them at their new positions, and possibly other points outside 'mask'.
+    fn wrap(mut self, wrapped: impl FnOnce() -> Vec<u64>, mask: &[u64]) -> Self {
+        if self.topology == Topology::Toroidal {
+            self.words
+                .iter_mut()
+                .zip(wrapped().iter().zip(mask))
+                .for_each(|(word, (w, m))| *word |= w & m);
+        }
+        self
+    }
+
+    // Points which are moved beyond the edges of the grid are dropped, or enter the grid again on
+    // the opposite side in the toroidal topology. Horizontal shifts must not wrap into the
+    // neighboring row, so the column which receives the wrapped bits is cleared.
     pub fn shift(&self, direction: Direction) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        if self.words.is_empty() {
+            // the grid has no points
+            return self.clone();
+        }
+
+        let masks = self.masks();
+
         match direction {
-            Direction::Left => Self {
-                dimensions: self.dimensions.clone(),
-                data: self.data.clone() >> 1,
-            },
-            Direction::Right => {
-                Self {
-                    dimensions: self.dimensions.clone(),
-                    data: self.data.clone() << 1,
-                } & self.dimensions.full()
-            }
-            Direction::Up => Self {
-                dimensions: self.dimensions.clone(),
-                data: self.data.clone() >> self.dimensions.width,
-            },
-            Direction::Down => {
-                Self {
-                    dimensions: self.dimensions.clone(),
-                    data: self.data.clone() << self.dimensions.width,
-                } & self.dimensions.full()
+            Direction::Left => self
+                .with_words(shift_down_bits(&self.words, 1))
+                .and_words(masks.left_columns(width - 1))
+                .wrap(|| shift_up_bits(&self.words, width - 1), masks.right_columns(1)),
+            Direction::Right => self
+                .with_words(shift_up_bits(&self.words, 1))
+                .and_words(masks.right_columns(width - 1))
+                .wrap(|| shift_down_bits(&self.words, width - 1), masks.left_columns(1)),
+            Direction::Up => self
+                .with_words(shift_down_bits(&self.words, width))
+                .wrap(|| shift_up_bits(&self.words, width * (height - 1)), &masks.full),
+            Direction::Down => self
+                .with_words(shift_up_bits(&self.words, width))
+                .and_words(&masks.full)
+                .wrap(|| shift_down_bits(&self.words, width * (height - 1)), &masks.full),
+            Direction::UpLeft => self.shift(Direction::Up).shift(Direction::Left),
+            Direction::UpRight => self.shift(Direction::Up).shift(Direction::Right),
+            Direction::DownLeft => self.shift(Direction::Down).shift(Direction::Left),
+            Direction::DownRight => self.shift(Direction::Down).shift(Direction::Right),
+        }
+    }
+
+    // The same as applying shift n times, but computed with a single bit shift per word
+    fn shift_by(&self, direction: Direction, n: usize) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        let toroidal = self.topology == Topology::Toroidal;
+        if self.words.is_empty() {
+            return self.clone();
+        }
+
+        let n = match direction {
+            _ if n <= 1 => return if n == 0 { self.clone() } else { self.shift(direction) },
+            Direction::Left | Direction::Right if toroidal => n % width,
+            Direction::Up | Direction::Down if toroidal => n % height,
+            Direction::Left | Direction::Right if n >= width => return self.empty_like(),
+            Direction::Up | Direction::Down if n >= height => return self.empty_like(),
+            _ => n,
+        };
+
+        let masks = self.masks();
+        let i = n as isize;
+
+        match direction {
+            Direction::Left => self
+                .with_words(shift_down_bits(&self.words, n))
+                .and_words(masks.left_columns(width - n))
+                .wrap(|| shift_up_bits(&self.words, width - n), masks.right_columns(n)),
+            Direction::Right => self
+                .with_words(shift_up_bits(&self.words, n))
+                .and_words(masks.right_columns(width - n))
+                .wrap(|| shift_down_bits(&self.words, width - n), masks.left_columns(n)),
+            Direction::Up => self
+                .with_words(shift_down_bits(&self.words, n * width))
+                .wrap(|| shift_up_bits(&self.words, (height - n) * width), &masks.full),
+            Direction::Down => self
+                .with_words(shift_up_bits(&self.words, n * width))
+                .and_words(&masks.full)
+                .wrap(|| shift_down_bits(&self.words, (height - n) * width), &masks.full),
+            // a single bit shift is enough if no points have to wrap around
+            Direction::UpLeft if !toroidal => self.clipped_translation(-i, -i),
+            Direction::UpRight if !toroidal => self.clipped_translation(i, -i),
+            Direction::DownLeft if !toroidal => self.clipped_translation(-i, i),
+            Direction::DownRight if !toroidal => self.clipped_translation(i, i),
+            Direction::UpLeft => self.shift_by(Direction::Up, n).shift_by(Direction::Left, n),
+            Direction::UpRight => self.shift_by(Direction::Up, n).shift_by(Direction::Right, n),
+            Direction::DownLeft => self.shift_by(Direction::Down, n).shift_by(Direction::Left, n),
+            Direction::DownRight => self.shift_by(Direction::Down, n).shift_by(Direction::Right, n),
+        }
+    }
+
+    // Move all points by (dx, dy). Points which are moved beyond the edges are dropped, or enter
+    // the grid again on the opposite side in the toroidal topology.
+    pub fn translate(&self, dx: isize, dy: isize) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        if self.words.is_empty() {
+            return self.clone();
+        }
+
+        match self.topology {
+            Topology::Bounded => self.clipped_translation(dx, dy),
+            Topology::Toroidal => {
+                // Each point is moved by one of these vectors, depending on the edges it crosses
+                let (width, height) = (width as isize, height as isize);
+                let (dx, dy) = (dx.rem_euclid(width), dy.rem_euclid(height));
+
+                [(dx, dy), (dx - width, dy), (dx, dy - height), (dx - width, dy - height)]
+                    .into_iter()
+                    .map(|(dx, dy)| self.clipped_translation(dx, dy))
+                    .reduce(|result, part| result | part)
+                    .unwrap()
             }
         }
     }
 
+    // Move all points by (dx, dy) with a single bit shift, and drop the points which leave the
+    // grid. Bits which are moved across the left or right edge end up in the wrong column of the
+    // neighboring row, so these columns are cleared.
+    fn clipped_translation(&self, dx: isize, dy: isize) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        if dx.unsigned_abs() >= width || dy.unsigned_abs() >= height {
+            return self.empty_like();
+        }
+
+        let offset = dx + dy * width as isize;
+        let words = if offset >= 0 {
+            shift_up_bits(&self.words, offset as usize)
+        } else {
+            shift_down_bits(&self.words, offset.unsigned_abs())
+        };
+
+        let columns = if dx >= 0 {
+            self.masks().right_columns(width - dx as usize)
+        } else {
+            self.masks().left_columns(width - dx.unsigned_abs())
+        };
+
+        self.with_words(words).and_words(columns)
+    }
+
+    // Add the orthogonal neighbors of all points
     pub fn shake(&self) -> Self {
-        self |
-            &self.shift(Direction::Left) |
-            &self.shift(Direction::Right) |
-            &self.shift(Direction::Up) |
-            &self.shift(Direction::Down)
+        let mut result = self.clone();
+        for direction in Direction::ORTHOGONAL {
+            result |= &self.shift(direction);
+        }
+        result
+    }
+
+    // Add the orthogonal and diagonal neighbors of all points
+    pub fn shake8(&self) -> Self {
+        let row = &(self | &self.shift(Direction::Left)) | &self.shift(Direction::Right);
+        &(&row | &row.shift(Direction::Up)) | &row.shift(Direction::Down)
+    }
+
+    // Move each point to f(point) in a grid with the given dimensions, keeping the topology
+    fn map_points(&self, dimensions: &Dimensions, f: impl Fn(Point) -> Point) -> Self {
+        PointSet::from_points(dimensions, self.iter().map(f)).with_topology(self.topology)
+    }
+
+    fn transposed_dimensions(&self) -> Dimensions {
+        Dimensions {
+            width: self.dimensions.height,
+            height: self.dimensions.width,
+        }
+    }
+
+    // Mirror the set at the main diagonal, i.e., (x, y) becomes (y, x)
+    pub fn transpose(&self) -> Self {
+        self.map_points(&self.transposed_dimensions(), |Point { x, y }| Point { x: y, y: x })
+    }
+
+    // Rotate the set by 90 degrees clockwise
+    pub fn rotate90(&self) -> Self {
+        let height = self.dimensions.height;
+        self.map_points(&self.transposed_dimensions(), |Point { x, y }| Point {
+            x: height - 1 - y,
+            y: x,
+        })
+    }
+
+    pub fn rotate180(&self) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        self.map_points(&self.dimensions, |Point { x, y }| Point {
+            x: width - 1 - x,
+            y: height - 1 - y,
+        })
+    }
+
+    // Swap the left and the right side
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.dimensions.width;
+        self.map_points(&self.dimensions, |Point { x, y }| Point { x: width - 1 - x, y })
+    }
+
+    // Swap the top and the bottom
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.dimensions.height;
+        self.map_points(&self.dimensions, |Point { x, y }| Point { x, y: height - 1 - y })
+    }
+
+    // The points in the rectangle with the given top left corner and size, in a grid which has the
+    // size of the rectangle. The rectangle must be inside the grid.
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        assert!(
+            x + width <= self.dimensions.width && y + height <= self.dimensions.height,
+            "Rectangle is not inside the grid"
+        );
+
+        let mut result = Dimensions { width, height }.empty().with_topology(self.topology);
+        copy_rectangle(self, Point { x, y }, &mut result, Point { x: 0, y: 0 }, width, height);
+        result
+    }
+
+    // Copy the set into an empty grid with the given dimensions, such that its top left corner is
+    // at 'at'. The set must fit into the new grid.
+    pub fn embed(&self, into: &Dimensions, at: Point) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        assert!(
+            at.x + width <= into.width && at.y + height <= into.height,
+            "Point set does not fit into the grid"
+        );
+
+        let mut result = into.empty().with_topology(self.topology);
+        copy_rectangle(self, Point { x: 0, y: 0 }, &mut result, at, width, height);
+        result
+    }
+
+    // Move each point (x, y) to (n * x, y) in a grid which is n times as wide. The other points of
+    // the wider grid are not in the result, e.g., to make the objects in a map twice as wide, they
+    // have to be added with shift(Direction::Right). For n = 0, the result is an empty set of width 0.
+    pub fn scale_x(&self, n: usize) -> Self {
+        let dimensions = Dimensions {
+            width: n * self.dimensions.width,
+            height: self.dimensions.height,
+        };
+        if n == 0 {
+            return dimensions.empty().with_topology(self.topology);
+        }
+        self.map_points(&dimensions, |Point { x, y }| Point { x: n * x, y })
+    }
+
+    // The points which can be reached from 'point' by orthogonal steps within this set. The result
+    // is empty if 'point' is not in the set.
+    pub fn component_containing(&self, point: &Point) -> Self {
+        self.flood_fill(point, Self::shake)
+    }
+
+    // The same as component_containing, but diagonal steps are also allowed
+    pub fn component_containing8(&self, point: &Point) -> Self {
+        self.flood_fill(point, Self::shake8)
+    }
+
+    // Split the set into its orthogonally connected components, which are returned in the
+    // row-major order of their first points.
+    pub fn components(&self) -> Vec<Self> {
+        self.split_components(Self::component_containing)
+    }
+
+    // The same as components, but points which touch diagonally are also connected
+    pub fn components8(&self) -> Vec<Self> {
+        self.split_components(Self::component_containing8)
+    }
+
+    fn flood_fill(&self, point: &Point, grow: fn(&Self) -> Self) -> Self {
+        let mut component = self.empty_like();
+        if self.contains(point) {
+            component.insert(point);
+        }
+
+        loop {
+            let grown = grow(&component) & self;
+            if grown == component {
+                return component;
+            }
+            component = grown;
+        }
+    }
+
+    fn split_components(&self, component_containing: fn(&Self, &Point) -> Self) -> Vec<Self> {
+        let mut remaining = self.clone();
+        let mut result = Vec::new();
+
+        while let Some(point) = remaining.first() {
+            let component = component_containing(&remaining, &point);
+            remaining &= &!&component;
+            result.push(component);
+        }
+
+        result
+    }
+
+    // Add all points within the given Manhattan distance, i.e., the same as applying shake r times
+    pub fn dilate_manhattan(&self, r: usize) -> Self {
+        self.dilate(&StructuringElement::Manhattan(r))
+    }
+
+    // Add all points within the given Chebyshev distance, i.e., the same as applying shake8 r times
+    pub fn dilate_chebyshev(&self, r: usize) -> Self {
+        self.dilate(&StructuringElement::Chebyshev(r))
+    }
+
+    // Keep only the points whose neighborhood within the given Manhattan distance is contained
+    // in the set. Points outside the grid are considered not to be in the set.
+    pub fn erode_manhattan(&self, r: usize) -> Self {
+        self.erode(&StructuringElement::Manhattan(r))
+    }
+
+    // The same as erode_manhattan, but for the Chebyshev distance
+    pub fn erode_chebyshev(&self, r: usize) -> Self {
+        self.erode(&StructuringElement::Chebyshev(r))
+    }
+
+    // The set of all points p + b, where p is in this set and b in the structuring element
+    pub fn dilate(&self, element: &StructuringElement) -> Self {
+        match element {
+            StructuringElement::Manhattan(r) => self.dilate_diamond(*r),
+            _ => self.morphology(element, false),
+        }
+    }
+
+    // The points with |dx| + |dy| <= 2j and an even dx + dy are a * (1, -1) + b * (1, 1) with
+    // -j <= a, b <= j. They are added with diagonal rays, which need only log(j) shifts, and one or
+    // two shakes add the remaining points of the diamond with radius r.
+    //
+    // In the bounded topology, the rays drop a point if the corner between them is outside of the
+    // grid. Then the corner on the other side is inside of the grid as long as width and height are
+    // at least 2j, so the rays are applied in both orders.
+    fn dilate_diamond(&self, r: usize) -> Self {
+        let Dimensions { width, height } = self.dimensions;
+        let bounded = self.topology == Topology::Bounded;
+
+        let mut j = r.saturating_sub(1) / 2;
+        if bounded {
+            j = j.min(width / 2).min(height / 2);
+        }
+
+        // The rays replace 2j shakes, but they are only cheaper from j = 3 on
+        if j < 3 {
+            j = 0;
+        }
+
+        let diagonal = |ps: &Self, direction: Direction| {
+            ps.ray(direction, j, false)
+                .ray(direction.opposite(), j, false)
+        };
+        let square = |first: Direction, second: Direction| diagonal(&diagonal(self, first), second);
+
+        let mut result = square(Direction::UpRight, Direction::DownRight);
+        if bounded {
+            result |= &square(Direction::DownRight, Direction::UpRight);
+        }
+
+        // more shakes than the grid is wide and high do not add any points
+        for _ in 0..(r - 2 * j).min(width + height) {
+            result = result.shake();
+        }
+
+        result
+    }
+
+    // The set of all points p such that p + b is in this set for all b in the structuring element.
+    // Points outside the grid are considered not to be in the set.
+    pub fn erode(&self, element: &StructuringElement) -> Self {
+        self.morphology(element, true)
+    }
+
+    fn morphology(&self, element: &StructuringElement, erode: bool) -> Self {
+        match element {
+            StructuringElement::Chebyshev(r) => self
+                .ray(Direction::Left, *r, erode)
+                .ray(Direction::Right, *r, erode)
+                .ray(Direction::Up, *r, erode)
+                .ray(Direction::Down, *r, erode),
+            StructuringElement::Manhattan(0) => self.clone(),
+            StructuringElement::Manhattan(r) => {
+                // The points with |dx| + |dy| <= k and dx + dy = k (mod 2) are (a + b - k, b - a)
+                // with 0 <= a, b <= k. The diamond with radius r is the union of these sets for
+                // k = r and k = r - 1. The translation comes last, because an eroded set must not
+                // extend beyond the grid at any time.
+                let translation = if erode { Direction::Right } else { Direction::Left };
+                let diagonal_square = |k: usize| {
+                    self.ray(Direction::UpRight, k, erode)
+                        .ray(Direction::DownRight, k, erode)
+                        .shift_by(translation, k)
+                };
+
+                if erode {
+                    diagonal_square(*r) & diagonal_square(r - 1)
+                } else {
+                    diagonal_square(*r) | diagonal_square(r - 1)
+                }
+            }
+            StructuringElement::Offsets(offsets) => {
+                let translated = offsets.iter().map(|(dx, dy)| {
+                    let (dx, dy) = if erode { (-dx, -dy) } else { (*dx, *dy) };
+                    let horizontal = if dx < 0 { Direction::Left } else { Direction::Right };
+                    let vertical = if dy < 0 { Direction::Up } else { Direction::Down };
+
+                    self.shift_by(horizontal, dx.unsigned_abs())
+                        .shift_by(vertical, dy.unsigned_abs())
+                });
+
+                if erode {
+                    translated.fold(!self.empty_like(), |acc, ps| acc & ps)
+                } else {
+                    translated.fold(self.empty_like(), |acc, ps| acc | ps)
+                }
+            }
+        }
+    }
+
+    // Dilation (or erosion) with the line segment from the origin to length * direction. The
+    // segment is composed of steps which double in size, such that only log(length) shifts are
+    // needed.
+    fn ray(&self, direction: Direction, length: usize, erode: bool) -> Self {
+        let direction = if erode { direction.opposite() } else { direction };
+
+        let mut result = self.clone();
+        let mut covered = 1;
+
+        while covered <= length {
+            let step = covered.min(length + 1 - covered);
+            let shifted = result.shift_by(direction, step);
+
+            if erode {
+                result &= &shifted;
+            } else {
+                result |= &shifted;
+            }
+
+            covered += step;
+        }
+
+        result
+    }
+
+    // Breadth-first search from the points in this set through 'passable'. The n-th item is the
+    // set of points whose shortest path from the start has n orthogonal steps. The iteration ends
+    // when no new points can be reached.
+    pub fn distance_layers<'a>(&self, passable: &'a PointSet) -> DistanceLayers<'a> {
+        DistanceLayers {
+            passable,
+            frontier: self.clone(),
+            visited: self.clone(),
+        }
+    }
+
+    // Number of steps from this set to the nearest point of 'goal', or None if 'goal' cannot be
+    // reached through 'passable'
+    pub fn distance_to(&self, goal: &PointSet, passable: &PointSet) -> Option<usize> {
+        self.distance_layers(passable)
+            .position(|layer| !(&layer & goal).is_empty())
+    }
+
+    // Shortest distance from this set for each point which can be reached through 'passable'
+    pub fn distance_map(&self, passable: &PointSet) -> Grid<Option<usize>> {
+        let mut distances = Grid::new(&self.dimensions, None);
+
+        for (distance, layer) in self.distance_layers(passable).enumerate() {
+            for point in &layer {
+                distances[point] = Some(distance);
+            }
+        }
+
+        distances
+    }
+
+    // The points of the set whose neighbor in the given direction is not in the set, i.e., the
+    // points which need a fence on that side.
+    pub fn boundary(&self, direction: Direction) -> Self {
+        self & &!self.shift(direction.opposite())
+    }
+
+    // Number of unit edges between points of the set and points outside of it
+    pub fn perimeter(&self) -> usize {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(|direction| self.boundary(direction).len())
+            .sum()
+    }
+
+    // Number of maximal straight fence segments around the set. A segment is counted at its first
+    // point, i.e., at the boundary point whose predecessor along the fence is not a boundary point.
+    pub fn sides(&self) -> usize {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(|direction| {
+                let boundary = self.boundary(direction);
+                let along = match direction {
+                    Direction::Left | Direction::Right => Direction::Down,
+                    _ => Direction::Right,
+                };
+
+                (&boundary & &!boundary.shift(along)).len()
+            })
+            .sum()
+    }
+
+    // Number of convex and concave corners of the set. For regions on a grid, this is always
+    // equal to the number of sides.
+    pub fn corners(&self) -> usize {
+        [
+            (Direction::Up, Direction::Left, Direction::UpLeft),
+            (Direction::Up, Direction::Right, Direction::UpRight),
+            (Direction::Down, Direction::Left, Direction::DownLeft),
+            (Direction::Down, Direction::Right, Direction::DownRight),
+        ]
+        .into_iter()
+        .map(|(vertical, horizontal, diagonal)| {
+            let convex = self.boundary(vertical) & self.boundary(horizontal);
+
+            // neighbors in both orthogonal directions are in the set, but the diagonal one is not
+            let concave = self
+                & &self.shift(vertical.opposite())
+                & &self.shift(horizontal.opposite())
+                & !self.shift(diagonal.opposite());
+
+            convex.len() + concave.len()
+        })
+        .sum()
+    }
+
+    // For each point of the grid, count how many of its eight neighbors are in this set.
+    pub fn neighbor_count(&self) -> NeighborCount {
+        let mut planes: [PointSet; 4] = std::array::from_fn(|_| self.empty_like());
+
+        for direction in Direction::ALL {
+            // add the shifted set to the counter, which is stored as a binary number per point
+            let mut carry = self.shift(direction);
+            for plane in planes.iter_mut() {
+                let new_carry = &*plane & &carry;
+                *plane ^= &carry;
+                carry = new_carry;
+            }
+        }
+
+        NeighborCount { planes }
+    }
+}
+
+// The neighborhood which is used for dilation and erosion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StructuringElement {
+    // all offsets with |dx| + |dy| <= r
+    Manhattan(usize),
+
+    // all offsets with max(|dx|, |dy|) <= r
+    Chebyshev(usize),
+
+    // arbitrary offsets (dx, dy)
+    Offsets(Vec<(isize, isize)>),
+}
+
+// Number of neighbors for each point of a grid. Bit i of the count is stored in planes[i], such
+// that queries like "fewer than 4 neighbors" can be answered with bitwise operations.
+pub struct NeighborCount {
+    planes: [PointSet; 4],
+}
+
+impl NeighborCount {
+    // All points with exactly n neighbors
+    pub fn exactly(&self, n: usize) -> PointSet {
+        if n > 8 {
+            return self.planes[0].empty_like();
+        }
+
+        self.planes
+            .iter()
+            .enumerate()
+            .fold(!self.planes[0].empty_like(), |acc, (i, plane)| {
+                if n >> i & 1 == 1 {
+                    acc & plane
+                } else {
+                    acc & !plane
+                }
+            })
+    }
+
+    // All points with less than n neighbors
+    pub fn fewer_than(&self, n: usize) -> PointSet {
+        (0..n.min(9)).fold(self.planes[0].empty_like(), |acc, k| {
+            acc | self.exactly(k)
+        })
+    }
+
+    // All points with n or more neighbors
+    pub fn at_least(&self, n: usize) -> PointSet {
+        !self.fewer_than(n)
+    }
+}
+
+// Iterator over the layers of a breadth-first search, see PointSet::distance_layers
+pub struct DistanceLayers<'a> {
+    passable: &'a PointSet,
+
+    // the points which were reached in the last step
+    frontier: PointSet,
+    visited: PointSet,
+}
+
+impl Iterator for DistanceLayers<'_> {
+    type Item = PointSet;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frontier.is_empty() {
+            return None;
+        }
+
+        let new = &(&self.frontier.shake() & self.passable) & &!&self.visited;
+        self.visited |= &new;
+
+        Some(std::mem::replace(&mut self.frontier, new))
     }
 }
 
+// Iterator over the points in a PointSet, which visits only the set bits.
+pub struct Points<'a> {
+    point_set: &'a PointSet,
+    word_index: usize,
+
+    // the bits of the current word which have not been visited yet
+    remaining: u64,
+}
+
+impl Iterator for Points<'_> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let words = &self.point_set.words;
+
+        while self.remaining == 0 {
+            self.word_index += 1;
+            self.remaining = *words.get(self.word_index)?;
+        }
+
+        let n = self.word_index * WORD_BITS + self.remaining.trailing_zeros() as usize;
+
+        // clear the lowest set bit
+        self.remaining &= self.remaining - 1;
+
+        Some(Point::from_bit_index(n, &self.point_set.dimensions))
+    }
+}
+
+impl<'a> IntoIterator for &'a PointSet {
+    type Item = Point;
+    type IntoIter = Points<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for PointSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.dimensions == other.dimensions
+            && self.topology == other.topology
+            && self.words == other.words
+    }
+}
+
+impl Eq for PointSet {}
+
 impl Debug for PointSet {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{{ ")?;
 
         let mut first = true;
-        for y in 0..self.dimensions.height {
-            for x in 0..self.dimensions.width {
-                if self.contains(&Point { x, y }) {
-                    if first {
-                        first = false;
-                    } else {
-                        write!(f, ", ")?;
-                    }
-
-                    write!(f, "({}, {})", x, y)?;
-                }
+        for Point { x, y } in self {
+            if first {
+                first = false;
+            } else {
+                write!(f, ", ")?;
             }
+
+            write!(f, "({}, {})", x, y)?;
         }
 
         write!(f, " }}")?;
@@ -225,19 +1226,16 @@ impl BitAnd<&PointSet> for &PointSet {
     type Output = PointSet;
 
     fn bitand(self, rhs: &PointSet) -> Self::Output {
-        assert_eq!(self.dimensions, rhs.dimensions);
-        PointSet {
-            dimensions: self.dimensions.clone(),
-            data: &self.data & &rhs.data,
-        }
+        self.clone() & rhs
     }
 }
 
 impl BitAnd<&PointSet> for PointSet {
     type Output = PointSet;
 
-    fn bitand(self, rhs: &PointSet) -> Self::Output {
-        &self & rhs
+    fn bitand(mut self, rhs: &PointSet) -> Self::Output {
+        self &= rhs;
+        self
     }
 }
 
@@ -245,7 +1243,7 @@ impl BitAnd for PointSet {
     type Output = PointSet;
 
     fn bitand(self, rhs: Self) -> Self::Output {
-        &self & &rhs
+        self & &rhs
     }
 }
 
@@ -253,19 +1251,16 @@ impl BitOr for &PointSet {
     type Output = PointSet;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        assert_eq!(self.dimensions, rhs.dimensions);
-        PointSet {
-            dimensions: self.dimensions.clone(),
-            data: &self.data | &rhs.data,
-        }
+        self.clone() | rhs
     }
 }
 
 impl BitOr<&PointSet> for PointSet {
     type Output = PointSet;
 
-    fn bitor(self, rhs: &PointSet) -> Self::Output {
-        &self | rhs
+    fn bitor(mut self, rhs: &PointSet) -> Self::Output {
+        self |= rhs;
+        self
     }
 }
 
@@ -273,87 +1268,255 @@ impl BitOr for PointSet {
     type Output = PointSet;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        &self | &rhs
+        self | &rhs
+    }
+}
+
+impl BitXor for &PointSet {
+    type Output = PointSet;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.clone() ^ rhs
     }
 }
 
-impl BitAndAssign<&PointSet> for PointSet {
-    fn bitand_assign(&mut self, rhs: &Self) {
-        // TODO: just manipulate data?
-        *self = &*self & rhs;
+impl BitXor<&PointSet> for PointSet {
+    type Output = PointSet;
+
+    fn bitxor(mut self, rhs: &PointSet) -> Self::Output {
+        self ^= rhs;
+        self
+    }
+}
+
+impl BitXor for PointSet {
+    type Output = PointSet;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self ^ &rhs
+    }
+}
+
+impl BitAndAssign<&PointSet> for PointSet {
+    fn bitand_assign(&mut self, rhs: &Self) {
+        assert_eq!(self.dimensions, rhs.dimensions);
+        self.words
+            .iter_mut()
+            .zip(&rhs.words)
+            .for_each(|(word, other)| *word &= other);
+    }
+}
+
+impl BitOrAssign<&PointSet> for PointSet {
+    fn bitor_assign(&mut self, rhs: &Self) {
+        assert_eq!(self.dimensions, rhs.dimensions);
+        self.words
+            .iter_mut()
+            .zip(&rhs.words)
+            .for_each(|(word, other)| *word |= other);
+    }
+}
+
+impl BitXorAssign<&PointSet> for PointSet {
+    fn bitxor_assign(&mut self, rhs: &Self) {
+        assert_eq!(self.dimensions, rhs.dimensions);
+        self.words
+            .iter_mut()
+            .zip(&rhs.words)
+            .for_each(|(word, other)| *word ^= other);
+    }
+}
+
+impl Not for &PointSet {
+    type Output = PointSet;
+
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+impl Not for PointSet {
+    type Output = PointSet;
+
+    fn not(mut self) -> Self::Output {
+        let masks = &self.masks;
+        self.words
+            .iter_mut()
+            .zip(&masks.full)
+            .for_each(|(word, full)| *word = !*word & full);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    RaggedRow {
+        row: usize,
+        width: usize,
+        expected_width: usize,
+    },
+    UnexpectedCharacter {
+        row: usize,
+        column: usize,
+        character: char,
+    },
+    PointCount {
+        character: char,
+        count: usize,
+    },
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "Could not parse empty rows"),
+            GridParseError::RaggedRow {
+                row,
+                width,
+                expected_width,
+            } => write!(
+                f,
+                "Row {} has width {}, but the first row has width {}",
+                row, width, expected_width
+            ),
+            GridParseError::UnexpectedCharacter {
+                row,
+                column,
+                character,
+            } => write!(
+                f,
+                "Unexpected character '{}' in row {}, column {}",
+                character, row, column
+            ),
+            GridParseError::PointCount { character, count } => write!(
+                f,
+                "Expected exactly one '{}', but found {}",
+                character, count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+// Check that a map with one character per point is not empty and that all rows have the same width,
+// and call 'accept' for each character in row-major order. If it returns false, an error with the
+// position of that character is returned.
+pub(crate) fn parse_map(
+    lines: &str,
+    mut accept: impl FnMut(&Dimensions, Point, char) -> bool,
+) -> Result<Dimensions, GridParseError> {
+    let height = lines.lines().count();
+    let width = lines.lines().next().map_or(0, |row| row.chars().count());
+
+    if width == 0 {
+        return Err(GridParseError::Empty);
+    }
+
+    let dimensions = Dimensions { width, height };
+
+    for (y, row) in lines.lines().enumerate() {
+        let row_width = row.chars().count();
+        if row_width != width {
+            return Err(GridParseError::RaggedRow {
+                row: y,
+                width: row_width,
+                expected_width: width,
+            });
+        }
+
+        for (x, c) in row.chars().enumerate() {
+            if !accept(&dimensions, Point { x, y }, c) {
+                return Err(GridParseError::UnexpectedCharacter {
+                    row: y,
+                    column: x,
+                    character: c,
+                });
+            }
+        }
+    }
+
+    Ok(dimensions)
+}
+
+// The points of a map, grouped by the character at each point
+pub struct PointSets {
+    pub dimensions: Dimensions,
+    sets: HashMap<char, PointSet>,
+}
+
+impl PointSets {
+    pub fn parse(lines: &str) -> Result<Self, GridParseError> {
+        Self::parse_impl(lines, None)
+    }
+
+    // The same as parse, but any character which is not in 'alphabet' is an error
+    pub fn parse_with_alphabet(lines: &str, alphabet: &str) -> Result<Self, GridParseError> {
+        Self::parse_impl(lines, Some(alphabet))
+    }
+
+    fn parse_impl(lines: &str, alphabet: Option<&str>) -> Result<Self, GridParseError> {
+        let mut sets: HashMap<char, PointSet> = HashMap::new();
+
+        let dimensions = parse_map(lines, |dimensions, point, c| {
+            if alphabet.is_some_and(|alphabet| !alphabet.contains(c)) {
+                return false;
+            }
+
+            sets.entry(c)
+                .or_insert_with(|| dimensions.empty())
+                .insert(&point);
+            true
+        })?;
+
+        Ok(Self { dimensions, sets })
+    }
+
+    // The characters which occur in the map
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.sets.keys().copied()
+    }
+
+    // The points with the given character. The set is empty if the character does not occur.
+    pub fn get(&self, c: char) -> PointSet {
+        self.sets
+            .get(&c)
+            .cloned()
+            .unwrap_or_else(|| self.dimensions.empty())
     }
-}
 
-impl BitOrAssign<&PointSet> for PointSet {
-    fn bitor_assign(&mut self, rhs: &Self) {
-        // TODO: just manipulate data?
-        *self = &*self | rhs;
+    // The same as get, but the set is moved out of the map
+    pub fn remove(&mut self, c: char) -> PointSet {
+        self.sets
+            .remove(&c)
+            .unwrap_or_else(|| self.dimensions.empty())
     }
-}
 
-impl Not for &PointSet {
-    type Output = PointSet;
+    // The position of a character which must occur exactly once, like the start of a maze
+    pub fn single_point(&self, c: char) -> Result<Point, GridParseError> {
+        let points = self.get(c);
 
-    fn not(self) -> Self::Output {
-        let PointSet { dimensions, data } = self;
-        let data = dimensions.full().data - data;
-        PointSet {
-            dimensions: dimensions.clone(),
-            data,
+        match points.len() {
+            1 => Ok(points.first().unwrap()),
+            count => Err(GridParseError::PointCount {
+                character: c,
+                count,
+            }),
         }
     }
 }
 
-impl Not for PointSet {
-    type Output = PointSet;
-
-    fn not(self) -> Self::Output {
-        let PointSet { dimensions, data } = self;
-        let data = dimensions.full().data - &data;
-        PointSet { dimensions, data }
+impl From<PointSets> for HashMap<char, PointSet> {
+    fn from(point_sets: PointSets) -> Self {
+        point_sets.sets
     }
 }
 
 pub fn parse_point_sets(lines: &str) -> HashMap<char, PointSet> {
-    let rows: Vec<Vec<char>> = lines
-        .lines()
-        .map(str::chars)
-        .map(Iterator::collect)
-        .collect();
-
-    let height = rows.len();
-    let width = rows
-        .iter()
-        .next()
-        .expect("Could not parse empty rows")
-        .len();
-
-    if rows.iter().any(|row| row.len() != width) {
-        panic!("Not all rows have the same width");
-    }
-
-    let distinct_chars: HashSet<char> = rows.iter().flatten().map(|c| *c).collect();
-
-    distinct_chars
-        .into_iter()
-        .map(|c| {
-            (
-                c,
-                PointSet::from_points(
-                    &Dimensions {
-                        width: width,
-                        height: height,
-                    },
-                    rows.iter().enumerate().flat_map(move |(y, row)| {
-                        row.iter()
-                            .enumerate()
-                            .filter(move |(_x, other_c)| **other_c == c)
-                            .map(move |(x, _other_c)| Point { x, y })
-                    }),
-                ),
-            )
-        })
-        .collect()
+    PointSets::parse(lines)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .into()
 }
 
 pub fn point_sets_map(sets: &Vec<(char, &PointSet)>) -> String {
@@ -507,6 +1670,690 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shift_does_not_wrap_rows() {
+        let d = Dimensions {
+            width: 3,
+            height: 3,
+        };
+        let left_column = d.column(0);
+        let right_column = d.column(2);
+
+        assert!(left_column.shift(Direction::Left).is_empty());
+        assert!(right_column.shift(Direction::Right).is_empty());
+
+        assert_eq!(left_column.shift(Direction::Right), d.column(1));
+        assert_eq!(right_column.shift(Direction::Left), d.column(1));
+
+        assert_eq!((&left_column | &right_column).shake(), d.full());
+        assert_eq!(
+            PointSet::from_point(&d, &Point { x: 0, y: 1 }).shake(),
+            PointSet::from_points(
+                &d,
+                [
+                    Point { x: 0, y: 0 },
+                    Point { x: 0, y: 1 },
+                    Point { x: 1, y: 1 },
+                    Point { x: 0, y: 2 }
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_masks_are_shared() {
+        let d = Dimensions {
+            width: 7,
+            height: 5,
+        };
+        let blocked = d.column(3);
+        let free = !&blocked.shift(Direction::Left);
+
+        assert!(Arc::ptr_eq(&blocked.masks, &d.empty().masks));
+        assert!(Arc::ptr_eq(&free.masks, &d.full().masks));
+        assert!(!Arc::ptr_eq(
+            &free.masks,
+            &Dimensions {
+                width: 5,
+                height: 7
+            }
+            .full()
+            .masks
+        ));
+
+        // the masks are computed again once no set uses them any more
+        let weak = Arc::downgrade(&free.masks);
+        drop((blocked, free));
+        assert_eq!(weak.strong_count(), 0);
+    }
+
+    #[test]
+    fn test_to_big_uint() {
+        let d = Dimensions {
+            width: 7,
+            height: 5,
+        };
+        assert_eq!(
+            Point { x: 2, y: 3 }.to_big_uint(&d),
+            BigUint::from(1u32) << 23
+        );
+    }
+
+    #[test]
+    fn test_zero_area() {
+        for d in [
+            Dimensions {
+                width: 0,
+                height: 3,
+            },
+            Dimensions {
+                width: 3,
+                height: 0,
+            },
+        ] {
+            let empty = d.empty();
+            assert!(empty.is_empty());
+            assert_eq!(d.full(), empty);
+            assert_eq!(!&empty, empty);
+
+            for topology in [Topology::Bounded, Topology::Toroidal] {
+                let ps = empty.clone().with_topology(topology);
+                for direction in Direction::ALL {
+                    assert!(ps.shift(direction).is_empty());
+                }
+                assert!(ps.translate(2, -1).is_empty());
+                assert!(ps.dilate_manhattan(2).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_across_words() {
+        let d = Dimensions {
+            width: 70,
+            height: 5,
+        };
+        let points = [
+            Point { x: 0, y: 0 },
+            Point { x: 63, y: 0 },
+            Point { x: 69, y: 0 },
+            Point { x: 58, y: 1 },
+            Point { x: 0, y: 2 },
+            Point { x: 1, y: 3 },
+            Point { x: 69, y: 4 },
+        ];
+        let ps = PointSet::from_point_refs(&d, &points);
+
+        let shifted = |dx: isize, dy: isize| {
+            PointSet::from_points(
+                &d,
+                points.iter().filter_map(|Point { x, y }| {
+                    let x = x.checked_add_signed(dx).filter(|x| *x < d.width)?;
+                    let y = y.checked_add_signed(dy).filter(|y| *y < d.height)?;
+                    Some(Point { x, y })
+                }),
+            )
+        };
+
+        assert_eq!(ps.shift(Direction::Left), shifted(-1, 0));
+        assert_eq!(ps.shift(Direction::Right), shifted(1, 0));
+        assert_eq!(ps.shift(Direction::Up), shifted(0, -1));
+        assert_eq!(ps.shift(Direction::Down), shifted(0, 1));
+        assert_eq!((!&ps).len(), 70 * 5 - points.len());
+    }
+
+    #[test]
+    fn test_diagonal_shift() {
+        let d = Dimensions {
+            width: 3,
+            height: 3,
+        };
+        let center = Point { x: 1, y: 1 };
+        let ps = PointSet::from_point(&d, &center);
+
+        for direction in Direction::ALL {
+            assert_eq!(
+                ps.shift(direction),
+                PointSet::from_point(&d, &(center + direction))
+            );
+        }
+
+        let corner = PointSet::from_point(&d, &Point { x: 0, y: 0 });
+        assert!(corner.shift(Direction::UpLeft).is_empty());
+        assert!(corner.shift(Direction::DownLeft).is_empty());
+        assert!(corner.shift(Direction::UpRight).is_empty());
+        assert_eq!(
+            corner.shift(Direction::DownRight),
+            PointSet::from_point(&d, &center)
+        );
+    }
+
+    #[test]
+    fn test_shake8() {
+        let d = Dimensions {
+            width: 4,
+            height: 3,
+        };
+
+        assert_eq!(
+            PointSet::from_point(&d, &Point { x: 1, y: 1 }).shake8(),
+            &d.full() & &!d.column(3)
+        );
+        assert_eq!(
+            PointSet::from_point(&d, &Point { x: 3, y: 0 }).shake8(),
+            PointSet::from_points(
+                &d,
+                [
+                    Point { x: 2, y: 0 },
+                    Point { x: 3, y: 0 },
+                    Point { x: 2, y: 1 },
+                    Point { x: 3, y: 1 },
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_neighbor_count() {
+        let map = "@@.@\n@@@.\n.@@@";
+        let rolls = parse_point_sets(map).remove(&'@').unwrap();
+        let counts = rolls.neighbor_count();
+
+        let expected = [[3, 4, 4, 1], [4, 6, 6, 4], [3, 4, 4, 2]];
+        for (y, row) in expected.iter().enumerate() {
+            for (x, n) in row.iter().enumerate() {
+                let p = Point { x, y };
+                assert!(counts.exactly(*n).contains(&p));
+                assert!(counts.fewer_than(*n + 1).contains(&p));
+                assert!(!counts.fewer_than(*n).contains(&p));
+                assert!(counts.at_least(*n).contains(&p));
+            }
+        }
+
+        assert_eq!(
+            (&rolls & &counts.fewer_than(4)).points(),
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 3, y: 0 },
+                Point { x: 3, y: 2 }
+            ]
+        );
+
+        // the planes could represent up to 15 neighbors, but there are at most 8
+        let full = rolls.dimensions.full().neighbor_count();
+        assert!(full.exactly(16).is_empty());
+        assert!(full.exactly(9).is_empty());
+        assert_eq!(full.at_least(9), rolls.dimensions.empty());
+    }
+
+    #[test]
+    fn test_components() {
+        let map = "AA.B\nA..B\n.A.B\nAA..";
+        let a = parse_point_sets(map).remove(&'A').unwrap();
+        let d = &a.dimensions;
+
+        let upper = PointSet::from_points(
+            d,
+            [Point { x: 0, y: 0 }, Point { x: 1, y: 0 }, Point { x: 0, y: 1 }],
+        );
+        let lower = PointSet::from_points(
+            d,
+            [Point { x: 1, y: 2 }, Point { x: 0, y: 3 }, Point { x: 1, y: 3 }],
+        );
+
+        assert_eq!(a.components(), vec![upper.clone(), lower.clone()]);
+        assert_eq!(a.components8(), vec![a.clone()]);
+
+        assert_eq!(a.component_containing(&Point { x: 1, y: 3 }), lower);
+        assert_eq!(a.component_containing8(&Point { x: 1, y: 3 }), a);
+        assert!(a.component_containing(&Point { x: 2, y: 2 }).is_empty());
+
+        assert!(d.empty().components().is_empty());
+        assert_eq!(d.full().components(), vec![d.full()]);
+    }
+
+    #[test]
+    fn test_region_geometry() {
+        let map = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+        let regions = parse_point_sets(map)
+            .values()
+            .flat_map(PointSet::components)
+            .collect_vec();
+
+        assert_eq!(regions.len(), 11);
+        assert_eq!(
+            regions.iter().map(|r| r.len() * r.perimeter()).sum::<usize>(),
+            1930
+        );
+        assert_eq!(
+            regions.iter().map(|r| r.len() * r.sides()).sum::<usize>(),
+            1206
+        );
+        assert!(regions.iter().all(|r| r.sides() == r.corners()));
+
+        // a region with a hole which touches the outer boundary diagonally
+        let map = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA";
+        let a = parse_point_sets(map).remove(&'A').unwrap();
+        assert_eq!(a.len(), 28);
+        assert_eq!(a.perimeter(), 40);
+        assert_eq!(a.sides(), 12);
+        assert_eq!(a.corners(), 12);
+    }
+
+    #[test]
+    fn test_distances() {
+        let map = "S.#.\n.##.\n...E\n###.";
+        let mut point_sets = parse_point_sets(map);
+        let start = point_sets.remove(&'S').unwrap();
+        let end = point_sets.remove(&'E').unwrap();
+        let walls = point_sets.remove(&'#').unwrap();
+        let passable = !&walls;
+
+        let layers = start.distance_layers(&passable).collect_vec();
+        assert_eq!(
+            layers.iter().map(PointSet::points).collect_vec(),
+            vec![
+                vec![Point { x: 0, y: 0 }],
+                vec![Point { x: 1, y: 0 }, Point { x: 0, y: 1 }],
+                vec![Point { x: 0, y: 2 }],
+                vec![Point { x: 1, y: 2 }],
+                vec![Point { x: 2, y: 2 }],
+                vec![Point { x: 3, y: 2 }],
+                vec![Point { x: 3, y: 1 }, Point { x: 3, y: 3 }],
+                vec![Point { x: 3, y: 0 }],
+            ]
+        );
+
+        assert_eq!(start.distance_to(&end, &passable), Some(5));
+        assert_eq!(start.distance_to(&start, &passable), Some(0));
+        assert_eq!(start.distance_to(&walls, &passable), None);
+
+        let distances = start.distance_map(&passable);
+        assert_eq!(distances[Point { x: 3, y: 0 }], Some(7));
+        assert_eq!(distances[Point { x: 2, y: 0 }], None);
+        assert_eq!(distances.get(&Point { x: 4, y: 0 }), None);
+        assert_eq!(distances.iter().filter_map(|(_, d)| *d).count(), 10);
+    }
+
+    #[test]
+    fn test_toroidal_shift() {
+        let d = Dimensions {
+            width: 3,
+            height: 2,
+        };
+        let ps = PointSet::from_points(&d, [Point { x: 0, y: 0 }, Point { x: 2, y: 1 }])
+            .with_topology(Topology::Toroidal);
+        let expected = |points: [Point; 2]| {
+            PointSet::from_points(&d, points).with_topology(Topology::Toroidal)
+        };
+
+        assert_eq!(
+            ps.shift(Direction::Left),
+            expected([Point { x: 2, y: 0 }, Point { x: 1, y: 1 }])
+        );
+        assert_eq!(
+            ps.shift(Direction::Right),
+            expected([Point { x: 1, y: 0 }, Point { x: 0, y: 1 }])
+        );
+        assert_eq!(
+            ps.shift(Direction::Up),
+            expected([Point { x: 0, y: 1 }, Point { x: 2, y: 0 }])
+        );
+        assert_eq!(
+            ps.shift(Direction::Down),
+            expected([Point { x: 0, y: 1 }, Point { x: 2, y: 0 }])
+        );
+        assert_eq!(
+            ps.shift(Direction::UpLeft),
+            expected([Point { x: 2, y: 1 }, Point { x: 1, y: 0 }])
+        );
+
+        // the bounded topology is not affected
+        let bounded = ps.clone().with_topology(Topology::Bounded);
+        assert_eq!(bounded.shift(Direction::Left).len(), 1);
+        assert_eq!(bounded.shift(Direction::Down).len(), 1);
+    }
+
+    #[test]
+    fn test_translate() {
+        let d = Dimensions {
+            width: 70,
+            height: 4,
+        };
+        let points = [(0, 0), (63, 0), (64, 0), (69, 1), (1, 2), (35, 3), (69, 3)]
+            .map(|(x, y)| Point { x, y });
+        let ps = PointSet::from_points(&d, points);
+
+        let (width, height) = (d.width as isize, d.height as isize);
+        for dx in [-75, -70, -69, -64, -7, -1, 0, 1, 6, 63, 64, 69, 140] {
+            for dy in [-5, -3, -1, 0, 1, 2, 4, 9] {
+                let moved = points.map(|p| (p.x as isize + dx, p.y as isize + dy));
+
+                let bounded = moved
+                    .iter()
+                    .filter(|(x, y)| (0..width).contains(x) && (0..height).contains(y))
+                    .map(|&(x, y)| Point { x: x as usize, y: y as usize });
+                assert_eq!(ps.translate(dx, dy), PointSet::from_points(&d, bounded));
+
+                let toroidal = moved.iter().map(|(x, y)| Point {
+                    x: x.rem_euclid(width) as usize,
+                    y: y.rem_euclid(height) as usize,
+                });
+                assert_eq!(
+                    ps.clone().with_topology(Topology::Toroidal).translate(dx, dy),
+                    PointSet::from_points(&d, toroidal).with_topology(Topology::Toroidal)
+                );
+            }
+        }
+
+        assert_eq!(ps.translate(3, 0), ps.shift_by(Direction::Right, 3));
+
+        let p = Point { x: 3, y: 2 };
+        assert_eq!(p + (-3, 5), Point { x: 0, y: 7 });
+        assert_eq!(p.checked_translate(-4, 0), None);
+        assert_eq!(
+            p.checked_translate(0, isize::MAX),
+            Some(Point {
+                x: 3,
+                y: 2 + isize::MAX as usize
+            })
+        );
+        assert_eq!(Point { x: usize::MAX, y: 0 }.checked_translate(1, 0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_offset_overflow() {
+        let _ = Point { x: 3, y: 2 } + (0, -3);
+    }
+
+    #[test]
+    fn test_toroidal_distances() {
+        let d = Dimensions {
+            width: 70,
+            height: 5,
+        };
+        let walls = d.column(1).with_topology(Topology::Toroidal);
+        let start =
+            PointSet::from_point(&d, &Point { x: 0, y: 0 }).with_topology(Topology::Toroidal);
+        let goal =
+            PointSet::from_point(&d, &Point { x: 2, y: 4 }).with_topology(Topology::Toroidal);
+
+        // the way around the wall crosses the left and the top edge
+        assert_eq!(start.distance_to(&goal, &!&walls), Some(68 + 1));
+        assert_eq!(start.shake().len(), 5);
+        assert_eq!(walls.components().len(), 1);
+    }
+
+    #[test]
+    fn test_mixed_topologies() {
+        let d = Dimensions {
+            width: 70,
+            height: 5,
+        };
+        let start =
+            PointSet::from_point(&d, &Point { x: 0, y: 0 }).with_topology(Topology::Toroidal);
+        let goal = PointSet::from_point(&d, &Point { x: 2, y: 4 });
+
+        // the result has the topology of the left operand
+        let passable = !&d.column(1);
+        assert_eq!(passable.topology(), Topology::Bounded);
+        assert_eq!(start.distance_to(&goal, &passable), Some(68 + 1));
+        assert_eq!((&start & &d.full()).topology(), Topology::Toroidal);
+        assert_eq!((&start | &d.column(0)).topology(), Topology::Toroidal);
+        assert_eq!((&start ^ &d.empty()), start);
+        assert_eq!((d.column(0) | &start).topology(), Topology::Bounded);
+        assert_eq!((&d.empty() ^ &start), start.with_topology(Topology::Bounded));
+        assert_eq!((&d.full() & &d.column(3)).topology(), Topology::Bounded);
+    }
+
+    // Brute-force dilation and erosion for comparison
+    fn naive_morphology(ps: &PointSet, offsets: &[(isize, isize)], erode: bool) -> PointSet {
+        let d = &ps.dimensions;
+        let toroidal = ps.topology() == Topology::Toroidal;
+        let neighbor = |Point { x, y }: Point, (dx, dy): (isize, isize)| {
+            let (x, y) = (x as isize + dx, y as isize + dy);
+            let (w, h) = (d.width as isize, d.height as isize);
+            if toroidal {
+                Some(Point {
+                    x: x.rem_euclid(w) as usize,
+                    y: y.rem_euclid(h) as usize,
+                })
+            } else if (0..w).contains(&x) && (0..h).contains(&y) {
+                Some(Point {
+                    x: x as usize,
+                    y: y as usize,
+                })
+            } else {
+                None
+            }
+        };
+
+        let all_points = d.full().points();
+        let points = all_points.into_iter().filter(|p| {
+            if erode {
+                offsets
+                    .iter()
+                    .all(|offset| neighbor(*p, *offset).is_some_and(|q| ps.contains(&q)))
+            } else {
+                offsets
+                    .iter()
+                    .any(|(dx, dy)| neighbor(*p, (-dx, -dy)).is_some_and(|q| ps.contains(&q)))
+            }
+        });
+
+        PointSet::from_points(d, points).with_topology(ps.topology())
+    }
+
+    #[test]
+    fn test_dilate_and_erode() {
+        let d = Dimensions {
+            width: 67,
+            height: 9,
+        };
+        let sparse = PointSet::from_points(
+            &d,
+            (0..d.width * d.height)
+                .filter(|n| n * 7919 % 23 == 0)
+                .map(|n| Point::from_bit_index(n, &d)),
+        );
+        let dense = !&sparse.shift(Direction::Right);
+
+        for topology in [Topology::Bounded, Topology::Toroidal] {
+            for ps in [&sparse, &dense] {
+                let ps = ps.clone().with_topology(topology);
+
+                for r in [0, 1, 2, 3, 4, 5, 8, 13, 30] {
+                    let r_signed = r as isize;
+                    let square = (-r_signed..=r_signed)
+                        .cartesian_product(-r_signed..=r_signed)
+                        .collect_vec();
+                    let diamond = square
+                        .iter()
+                        .copied()
+                        .filter(|(dx, dy)| dx.abs() + dy.abs() <= r_signed)
+                        .collect_vec();
+
+                    assert_eq!(ps.dilate_manhattan(r), naive_morphology(&ps, &diamond, false));
+                    assert_eq!(ps.dilate_chebyshev(r), naive_morphology(&ps, &square, false));
+                    assert_eq!(ps.erode_manhattan(r), naive_morphology(&ps, &diamond, true));
+                    assert_eq!(ps.erode_chebyshev(r), naive_morphology(&ps, &square, true));
+                }
+
+                let knight = vec![(1, 2), (2, -1), (-1, -2), (-2, 1), (0, 0)];
+                let element = StructuringElement::Offsets(knight.clone());
+                assert_eq!(ps.dilate(&element), naive_morphology(&ps, &knight, false));
+                assert_eq!(ps.erode(&element), naive_morphology(&ps, &knight, true));
+            }
+        }
+
+        let single = PointSet::from_point(&d, &Point { x: 3, y: 4 });
+        let mut shaken = single.clone();
+        for _ in 0..3 {
+            shaken = shaken.shake();
+        }
+        assert_eq!(single.dilate_manhattan(3), shaken);
+        assert_eq!(single.dilate_manhattan(3).len(), 25);
+        assert_eq!(single.dilate_chebyshev(3).len(), 49);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(PointSets::parse("").err(), Some(GridParseError::Empty));
+        assert_eq!(
+            PointSets::parse("###\n#.\n###").err(),
+            Some(GridParseError::RaggedRow {
+                row: 1,
+                width: 2,
+                expected_width: 3
+            })
+        );
+        assert_eq!(
+            PointSets::parse_with_alphabet("###\n#.#\n#x#", "#.").err(),
+            Some(GridParseError::UnexpectedCharacter {
+                row: 2,
+                column: 1,
+                character: 'x'
+            })
+        );
+        assert_eq!(
+            GridParseError::UnexpectedCharacter {
+                row: 2,
+                column: 1,
+                character: 'x'
+            }
+            .to_string(),
+            "Unexpected character 'x' in row 2, column 1"
+        );
+    }
+
+    #[test]
+    fn test_point_sets() {
+        let mut point_sets =
+            PointSets::parse_with_alphabet("#####\n#S.E#\n#####\n", "#.SEX").unwrap();
+
+        assert_eq!(
+            point_sets.dimensions,
+            Dimensions {
+                width: 5,
+                height: 3
+            }
+        );
+        assert_eq!(point_sets.chars().sorted().collect::<String>(), "#.ES");
+
+        assert_eq!(point_sets.single_point('S'), Ok(Point { x: 1, y: 1 }));
+        assert_eq!(
+            point_sets.single_point('#'),
+            Err(GridParseError::PointCount {
+                character: '#',
+                count: 12
+            })
+        );
+        assert_eq!(
+            point_sets.single_point('X'),
+            Err(GridParseError::PointCount {
+                character: 'X',
+                count: 0
+            })
+        );
+
+        assert!(point_sets.get('X').is_empty());
+        assert_eq!(point_sets.remove('E').len(), 1);
+        assert!(point_sets.get('E').is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let d = Dimensions {
+            width: 70,
+            height: 3,
+        };
+        let points = vec![
+            Point { x: 0, y: 0 },
+            Point { x: 63, y: 0 },
+            Point { x: 64, y: 0 },
+            Point { x: 57, y: 1 },
+            Point { x: 58, y: 1 },
+            Point { x: 69, y: 2 },
+        ];
+        let ps = PointSet::from_point_refs(&d, &points);
+
+        assert_eq!(ps.iter().collect_vec(), points);
+        assert_eq!((&ps).into_iter().count(), points.len());
+        assert_eq!(ps.points(), points);
+
+        assert_eq!(ps.first(), Some(points[0]));
+        assert_eq!(ps.last(), Some(points[5]));
+        for (n, p) in points.iter().enumerate() {
+            assert_eq!(ps.nth(n), Some(*p));
+        }
+        assert_eq!(ps.nth(points.len()), None);
+
+        let empty = d.empty();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+        assert_eq!(empty.nth(0), None);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let parse = |map: &str| parse_point_sets(map).remove(&'#').unwrap();
+        let map = |ps: &PointSet| point_sets_map(&vec![('#', ps)]).replace(' ', ".");
+
+        let ps = parse("##.\n..#");
+
+        assert_eq!(map(&ps.transpose()), "#.\n#.\n.#");
+        assert_eq!(map(&ps.rotate90()), ".#\n.#\n#.");
+        assert_eq!(map(&ps.rotate180()), "#..\n.##");
+        assert_eq!(map(&ps.flip_horizontal()), ".##\n#..");
+        assert_eq!(map(&ps.flip_vertical()), "..#\n##.");
+        assert_eq!(map(&ps.crop(1, 0, 2, 2)), "#.\n.#");
+        assert_eq!(map(&ps.crop(0, 1, 3, 1)), "..#");
+        assert_eq!(
+            map(&ps.embed(&Dimensions { width: 5, height: 3 }, Point { x: 1, y: 1 })),
+            ".....\n.##..\n...#."
+        );
+        assert_eq!(map(&ps.scale_x(2)), "#.#...\n....#.");
+
+        let scaled = ps.scale_x(0);
+        assert!(scaled.is_empty());
+        assert_eq!(scaled.dimensions, Dimensions { width: 0, height: 2 });
+
+        assert_eq!(ps.rotate90().rotate90(), ps.rotate180());
+        assert_eq!(ps.rotate180().rotate180(), ps);
+        assert_eq!(ps.rotate90().flip_horizontal(), ps.transpose());
+        assert_eq!(ps.transpose().transpose(), ps);
+
+        let toroidal = ps.with_topology(Topology::Toroidal);
+        assert_eq!(toroidal.rotate90().topology(), Topology::Toroidal);
+        assert_eq!(toroidal.crop(0, 0, 2, 2).topology(), Topology::Toroidal);
+
+        // crop and embed across word boundaries
+        let d = Dimensions {
+            width: 70,
+            height: 3,
+        };
+        let ps = PointSet::from_points(
+            &d,
+            [(0, 0), (63, 0), (64, 0), (57, 1), (58, 1), (69, 2)].map(|(x, y)| Point { x, y }),
+        );
+        let window = (50..70).fold(d.empty(), |window, x| window | d.column(x));
+        assert_eq!(ps.crop(50, 0, 20, 3).len(), 5);
+        assert_eq!(ps.crop(50, 0, 20, 3).embed(&d, Point { x: 50, y: 0 }), &ps & &window);
+        assert_eq!(ps.flip_horizontal().flip_horizontal(), ps);
+    }
+
     #[test]
     fn test_from_points() {
         let d = Dimensions {
@@ -527,6 +2374,34 @@ mod tests {
         assert!(!point_set.contains(&Point { x: 0, y: 1 }));
     }
 
+    #[test]
+    #[should_panic(expected = "Point out of bounds")]
+    fn test_from_points_below_last_row() {
+        // the padding bits of the last word must not be used for points in row 'height'
+        let d = Dimensions {
+            width: 3,
+            height: 2,
+        };
+        PointSet::from_point(&d, &Point { x: 0, y: 2 });
+    }
+
+    #[test]
+    fn test_add_direction() {
+        let p = Point { x: 0, y: 1 };
+        assert_eq!(p + Direction::Right, Point { x: 1, y: 1 });
+        assert_eq!(p + Direction::Up, Point { x: 0, y: 0 });
+        assert_eq!(p + Direction::DownRight, Point { x: 1, y: 2 });
+        assert_eq!(p.checked_add(Direction::UpRight), Some(Point { x: 1, y: 0 }));
+        assert_eq!(p.checked_add(Direction::Left), None);
+        assert_eq!(p.checked_add(Direction::DownLeft), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot move Point { x: 0, y: 1 } Left")]
+    fn test_add_direction_outside_grid() {
+        let _ = Point { x: 0, y: 1 } + Direction::Left;
+    }
+
     #[test]
     fn test_parse_point_sets() {
         let map = "AAA.\n.BB.\n.CCC";
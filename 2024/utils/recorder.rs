@@ -0,0 +1,295 @@
+use crate::pointset::{point_sets_map, PointSet};
+use std::fmt::Write as _;
+use std::io::Write;
+
+#[cfg(feature = "gif")]
+use crate::image::{Image, Palette, Rgb};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub label: String,
+    pub map: String,
+}
+
+// Collects the successive states of a simulation, such that they can be replayed later
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Records the output of point_sets_map for the given sets
+    pub fn record(&mut self, label: impl Into<String>, sets: &Vec<(char, &PointSet)>) {
+        self.record_map(label, point_sets_map(sets));
+    }
+
+    pub fn record_map(&mut self, label: impl Into<String>, map: String) {
+        self.frames.push(Frame {
+            label: label.into(),
+            map,
+        });
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // Writes an asciinema cast file (version 2), which shows each frame with its label for the
+    // given number of seconds. It can be replayed with 'asciinema play'.
+    pub fn write_asciicast(
+        &self,
+        mut writer: impl Write,
+        seconds_per_frame: f64,
+    ) -> std::io::Result<()> {
+        let width = self
+            .frames
+            .iter()
+            .flat_map(|frame| [frame.label.as_str()].into_iter().chain(frame.map.lines()))
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = self
+            .frames
+            .iter()
+            .map(|frame| frame.map.lines().count() + 1)
+            .max()
+            .unwrap_or(0);
+
+        writeln!(
+            writer,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+            width, height
+        )?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            // Clear the screen and move the cursor to the top left corner before each frame
+            let mut output = String::from("\x1b[2J\x1b[H");
+            output.push_str(&frame.label);
+            for row in frame.map.lines() {
+                output.push_str("\r\n");
+                output.push_str(row);
+            }
+
+            writeln!(
+                writer,
+                "[{:.3}, \"o\", {}]",
+                i as f64 * seconds_per_frame,
+                json_string(&output)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Writes an animated GIF, where each character is drawn with its color from the palette. The
+    // labels are not shown. The delay between frames is given in hundredths of a second.
+    #[cfg(feature = "gif")]
+    pub fn write_gif(
+        &self,
+        writer: impl Write,
+        palette: &Palette,
+        cell_size: usize,
+        delay: u16,
+    ) -> std::io::Result<()> {
+        let images = self
+            .frames
+            .iter()
+            .map(|frame| Image::from_map(&frame.map, palette, cell_size))
+            .collect::<Vec<_>>();
+
+        let Some(first) = images.first() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Cannot write a GIF without frames",
+            ));
+        };
+
+        // The maps use only a few colors, so all frames can share one global color table
+        let mut colors: Vec<Rgb> = Vec::new();
+        for image in &images {
+            for pixel in image.pixels() {
+                if !colors.contains(pixel) {
+                    colors.push(*pixel);
+                }
+            }
+        }
+
+        if colors.len() > 256 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "A GIF cannot have more than 256 colors",
+            ));
+        }
+
+        let (Ok(width), Ok(height)) = (u16::try_from(first.width), u16::try_from(first.height)) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "A GIF cannot be wider or higher than 65535 pixels",
+            ));
+        };
+        let mut encoder = gif::Encoder::new(writer, width, height, colors.as_flattened())
+            .map_err(std::io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(std::io::Error::other)?;
+
+        for image in &images {
+            if (image.width, image.height) != (first.width, first.height) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "All frames of a GIF must have the same size",
+                ));
+            }
+
+            let mut frame = gif::Frame {
+                width,
+                height,
+                delay,
+                ..gif::Frame::default()
+            };
+            frame.buffer = image
+                .pixels()
+                .iter()
+                .map(|pixel| colors.iter().position(|color| color == pixel).unwrap() as u8)
+                .collect::<Vec<_>>()
+                .into();
+
+            encoder.write_frame(&frame).map_err(std::io::Error::other)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Quotes a string for JSON, escaping control characters like the newlines and the escape
+// sequences in the frames
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            c if c.is_control() => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointset::{Dimensions, Direction, Point};
+
+    fn robot_frames() -> Recorder {
+        let dimensions = Dimensions {
+            width: 3,
+            height: 2,
+        };
+        let walls = PointSet::from_point(&dimensions, &Point { x: 2, y: 0 });
+        let mut robot = PointSet::from_point(&dimensions, &Point { x: 0, y: 1 });
+
+        let mut recorder = Recorder::new();
+        recorder.record("Initial state", &vec![('#', &walls), ('@', &robot)]);
+        for step in 1..=2 {
+            robot = robot.shift(Direction::Right);
+            recorder.record(
+                format!("Step {}", step),
+                &vec![('#', &walls), ('@', &robot)],
+            );
+        }
+
+        recorder
+    }
+
+    #[test]
+    fn test_record() {
+        let recorder = robot_frames();
+
+        assert_eq!(recorder.len(), 3);
+        assert_eq!(
+            recorder.frames()[2],
+            Frame {
+                label: "Step 2".to_string(),
+                map: "  #\n  @".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_asciicast() {
+        let mut cast = Vec::new();
+        robot_frames().write_asciicast(&mut cast, 0.5).unwrap();
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines = cast.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "{\"version\": 2, \"width\": 13, \"height\": 3}");
+        assert_eq!(
+            lines[2],
+            "[0.500, \"o\", \"\\u001b[2J\\u001b[HStep 1\\r\\n  #\\r\\n @ \"]"
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn test_gif() {
+        let palette = Palette::default().with('@', [255, 0, 0]);
+
+        let mut data = Vec::new();
+        robot_frames()
+            .write_gif(&mut data, &palette, 2, 50)
+            .unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(data.as_slice())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 4));
+
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 50);
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+
+        assert!(Recorder::new()
+            .write_gif(Vec::new(), &palette, 2, 50)
+            .is_err());
+
+        // the size of a GIF is stored in 16 bits
+        let wide = Dimensions {
+            width: 1 << 16,
+            height: 1,
+        };
+        let mut recorder = Recorder::new();
+        recorder.record("Wide", &vec![('@', &wide.empty())]);
+        let error = recorder
+            .write_gif(Vec::new(), &palette, 1, 50)
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("65535"));
+    }
+}
@@ -0,0 +1,275 @@
+// Serde support for PointSet (requires the "serde" feature).
+//
+// By default, a PointSet is serialized compactly as its dimensions, its topology, and the lengths
+// of the alternating runs of absent and present points in row-major order, starting with absent
+// points:
+//
+//   {"dimensions":{"width":3,"height":2},"topology":"Bounded","runs":[1,2,2,1]}
+//
+// For snapshots which should be readable in a diff, annotate a field with
+// #[serde(with = "utils::serialization::ascii_map")] to store the set as a map string instead.
+
+use crate::pointset::{Dimensions, Point, PointSet, PointSets, Topology};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Larger grids are rejected, such that untrusted input cannot allocate arbitrary amounts of memory
+const MAX_POINTS: usize = 1 << 30;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "PointSet")]
+struct CompactPointSet {
+    dimensions: Dimensions,
+    topology: Topology,
+    runs: Vec<usize>,
+}
+
+impl From<&PointSet> for CompactPointSet {
+    fn from(point_set: &PointSet) -> Self {
+        let mut runs = Vec::new();
+        let mut next = 0;
+
+        for point in point_set {
+            let n = point.bit_index(&point_set.dimensions);
+            if n == next && !runs.is_empty() {
+                // extend the current run of present points
+                *runs.last_mut().unwrap() += 1;
+            } else {
+                runs.push(n - next);
+                runs.push(1);
+            }
+            next = n + 1;
+        }
+
+        Self {
+            dimensions: point_set.dimensions.clone(),
+            topology: point_set.topology(),
+            runs,
+        }
+    }
+}
+
+impl TryFrom<CompactPointSet> for PointSet {
+    type Error = String;
+
+    fn try_from(compact: CompactPointSet) -> Result<Self, Self::Error> {
+        let dimensions = compact.dimensions;
+        let size = dimensions
+            .width
+            .checked_mul(dimensions.height)
+            .filter(|size| *size <= MAX_POINTS)
+            .ok_or_else(|| {
+                format!(
+                    "A grid with width {} and height {} is not supported",
+                    dimensions.width, dimensions.height
+                )
+            })?;
+
+        let total = compact
+            .runs
+            .iter()
+            .try_fold(0usize, |total, run| total.checked_add(*run));
+        if total.is_none_or(|total| total > size) {
+            return Err(format!(
+                "The runs cover more points than the grid, which has only {}",
+                size
+            ));
+        }
+
+        let mut point_set = dimensions.empty().with_topology(compact.topology);
+        let mut n = 0;
+        for (i, run) in compact.runs.iter().enumerate() {
+            if i % 2 == 1 {
+                point_set.insert_bit_range(n..n + run);
+            }
+            n += run;
+        }
+
+        Ok(point_set)
+    }
+}
+
+impl Serialize for PointSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CompactPointSet::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PointSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PointSet::try_from(CompactPointSet::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+// Serializes a PointSet as a map string, with '#' for points in the set and '.' for all others.
+// The map cannot store the topology or the dimensions of a grid without points, so toroidal sets
+// and sets with a width or height of 0 are rejected when they are serialized.
+pub mod ascii_map {
+    use super::*;
+
+    pub fn to_string(point_set: &PointSet) -> String {
+        let Dimensions { width, height } = point_set.dimensions;
+
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        if point_set.contains(&Point { x, y }) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn serialize<S: Serializer>(
+        point_set: &PointSet,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        if point_set.topology() != Topology::Bounded {
+            return Err(S::Error::custom("ascii_map cannot store a toroidal PointSet"));
+        }
+        if point_set.dimensions.width == 0 || point_set.dimensions.height == 0 {
+            return Err(S::Error::custom(format!(
+                "ascii_map cannot store a PointSet of size {}x{}",
+                point_set.dimensions.width, point_set.dimensions.height
+            )));
+        }
+
+        serializer.serialize_str(&to_string(point_set))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PointSet, D::Error> {
+        let map = String::deserialize(deserializer)?;
+        let point_sets = PointSets::parse_with_alphabet(&map, "#.").map_err(D::Error::custom)?;
+        Ok(point_sets.get('#'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointset::Direction;
+
+    fn example() -> PointSet {
+        PointSets::parse_with_alphabet(".##.\n#...\n...#", "#.")
+            .unwrap()
+            .get('#')
+    }
+
+    #[test]
+    fn test_compact() {
+        let point_set = example();
+
+        let json = serde_json::to_string(&point_set).unwrap();
+        assert_eq!(
+            json,
+            r#"{"dimensions":{"width":4,"height":3},"topology":"Bounded","runs":[1,2,1,1,6,1]}"#
+        );
+        assert_eq!(serde_json::from_str::<PointSet>(&json).unwrap(), point_set);
+
+        let empty = point_set
+            .dimensions
+            .empty()
+            .with_topology(Topology::Toroidal);
+        let json = serde_json::to_string(&empty).unwrap();
+        assert!(json.ends_with(r#""topology":"Toroidal","runs":[]}"#));
+        assert_eq!(serde_json::from_str::<PointSet>(&json).unwrap(), empty);
+
+        let full = point_set.dimensions.full();
+        let json = serde_json::to_string(&full).unwrap();
+        assert!(json.ends_with(r#""runs":[0,12]}"#));
+        assert_eq!(serde_json::from_str::<PointSet>(&json).unwrap(), full);
+
+        // long runs are filled word by word
+        let wide = r#"{"dimensions":{"width":100,"height":3},"topology":"Bounded","runs":[5,290,3,1]}"#;
+        let wide = serde_json::from_str::<PointSet>(wide).unwrap();
+        assert_eq!(wide.len(), 291);
+        assert_eq!(wide.first(), Some(Point { x: 5, y: 0 }));
+        assert!(!wide.contains(&Point { x: 95, y: 2 }));
+        assert_eq!(wide.last(), Some(Point { x: 98, y: 2 }));
+
+        let too_long = r#"{"dimensions":{"width":2,"height":2},"topology":"Bounded","runs":[3,2]}"#;
+        assert!(serde_json::from_str::<PointSet>(too_long).is_err());
+
+        // grids without points are valid, but no run may be longer than zero
+        for dimensions in [
+            Dimensions {
+                width: 0,
+                height: 3,
+            },
+            Dimensions {
+                width: 3,
+                height: 0,
+            },
+        ] {
+            let empty = dimensions.empty();
+            let json = serde_json::to_string(&empty).unwrap();
+            assert!(json.ends_with(r#""runs":[]}"#));
+            assert_eq!(serde_json::from_str::<PointSet>(&json).unwrap(), empty);
+        }
+
+        let invalid = [
+            r#"{"dimensions":{"width":0,"height":3},"topology":"Bounded","runs":[0,1]}"#,
+            r#"{"dimensions":{"width":4294967296,"height":4294967296},"topology":"Bounded","runs":[]}"#,
+            r#"{"dimensions":{"width":2,"height":2},"topology":"Bounded","runs":[18446744073709551615,2]}"#,
+        ];
+        for json in invalid {
+            assert!(serde_json::from_str::<PointSet>(json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_ascii_map() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Snapshot {
+            direction: Direction,
+            #[serde(with = "ascii_map")]
+            walls: PointSet,
+        }
+
+        let snapshot = Snapshot {
+            direction: Direction::Up,
+            walls: example(),
+        };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(json, r#"{"direction":"Up","walls":".##.\n#...\n...#"}"#);
+        assert_eq!(serde_json::from_str::<Snapshot>(&json).unwrap(), snapshot);
+
+        assert!(
+            serde_json::from_str::<Snapshot>(r##"{"direction":"Up","walls":"#.\n#"}"##).is_err()
+        );
+
+        // sets which cannot be restored from a map are rejected instead of changing on a round trip
+        let toroidal = Snapshot {
+            direction: Direction::Up,
+            walls: example().with_topology(Topology::Toroidal),
+        };
+        let error = serde_json::to_string(&toroidal).unwrap_err();
+        assert_eq!(error.to_string(), "ascii_map cannot store a toroidal PointSet");
+
+        for dimensions in [
+            Dimensions {
+                width: 0,
+                height: 3,
+            },
+            Dimensions {
+                width: 3,
+                height: 0,
+            },
+        ] {
+            let zero_area = Snapshot {
+                direction: Direction::Up,
+                walls: dimensions.empty(),
+            };
+            assert!(serde_json::to_string(&zero_area).is_err());
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::pointset::{Dimensions, Direction, Point, PointSet};
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Debug;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Sub};
+
+// A point with signed coordinates, which can be moved anywhere on the infinite plane
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct IPoint {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl IPoint {
+    pub fn manhattan_distance(&self, other: &IPoint) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add<Direction> for IPoint {
+    type Output = IPoint;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        let IPoint { x, y } = self;
+
+        match rhs {
+            Direction::Left => IPoint { x: x - 1, y },
+            Direction::Right => IPoint { x: x + 1, y },
+            Direction::Up => IPoint { x, y: y - 1 },
+            Direction::Down => IPoint { x, y: y + 1 },
+            Direction::UpLeft => IPoint { x: x - 1, y: y - 1 },
+            Direction::UpRight => IPoint { x: x + 1, y: y - 1 },
+            Direction::DownLeft => IPoint { x: x - 1, y: y + 1 },
+            Direction::DownRight => IPoint { x: x + 1, y: y + 1 },
+        }
+    }
+}
+
+impl Add for IPoint {
+    type Output = IPoint;
+
+    fn add(self, rhs: IPoint) -> Self::Output {
+        IPoint {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for IPoint {
+    type Output = IPoint;
+
+    fn sub(self, rhs: IPoint) -> Self::Output {
+        IPoint {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl From<Point> for IPoint {
+    fn from(Point { x, y }: Point) -> Self {
+        IPoint {
+            x: x as i64,
+            y: y as i64,
+        }
+    }
+}
+
+impl TryFrom<IPoint> for Point {
+    type Error = String;
+
+    fn try_from(IPoint { x, y }: IPoint) -> Result<Self, Self::Error> {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => Ok(Point { x, y }),
+            _ => Err(format!("Cannot convert ({}, {}) into a Point", x, y)),
+        }
+    }
+}
+
+// A set of points on the infinite plane. Unlike PointSet, it does not have fixed dimensions: the
+// bounding box grows with the points which are inserted.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SparsePointSet {
+    points: HashSet<IPoint>,
+
+    // The number of points in each row and column which contains points. The bounding box is given
+    // by their first and last keys.
+    rows: BTreeMap<i64, usize>,
+    columns: BTreeMap<i64, usize>,
+}
+
+impl SparsePointSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = IPoint>) -> Self {
+        let mut result = Self::new();
+        for point in points {
+            result.insert(point);
+        }
+        result
+    }
+
+    // Returns true if the point was not in the set before
+    pub fn insert(&mut self, point: IPoint) -> bool {
+        if !self.points.insert(point) {
+            return false;
+        }
+
+        *self.rows.entry(point.y).or_default() += 1;
+        *self.columns.entry(point.x).or_default() += 1;
+        true
+    }
+
+    // Returns true if the point was in the set
+    pub fn remove(&mut self, point: &IPoint) -> bool {
+        if !self.points.remove(point) {
+            return false;
+        }
+
+        for (counts, key) in [(&mut self.rows, point.y), (&mut self.columns, point.x)] {
+            let count = counts.get_mut(&key).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&key);
+            }
+        }
+
+        true
+    }
+
+    pub fn contains(&self, point: &IPoint) -> bool {
+        self.points.contains(point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    // Smallest and largest coordinates of all points, or None if the set is empty
+    pub fn bounding_box(&self) -> Option<(IPoint, IPoint)> {
+        let (min_y, max_y) = (self.rows.first_key_value()?.0, self.rows.last_key_value()?.0);
+        let (min_x, max_x) = (self.columns.first_key_value()?.0, self.columns.last_key_value()?.0);
+
+        Some((
+            IPoint { x: *min_x, y: *min_y },
+            IPoint { x: *max_x, y: *max_y },
+        ))
+    }
+
+    // The points in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &IPoint> + '_ {
+        self.points.iter()
+    }
+
+    // The points in row-major order
+    pub fn points(&self) -> Vec<IPoint> {
+        self.points
+            .iter()
+            .copied()
+            .sorted_by_key(|IPoint { x, y }| (*y, *x))
+            .collect()
+    }
+
+    pub fn shift(&self, direction: Direction) -> Self {
+        Self::from_points(self.points.iter().map(|p| *p + direction))
+    }
+
+    pub fn translate(&self, dx: i64, dy: i64) -> Self {
+        let offset = IPoint { x: dx, y: dy };
+        Self::from_points(self.points.iter().map(|p| *p + offset))
+    }
+
+    // Add the orthogonal neighbors of all points
+    pub fn shake(&self) -> Self {
+        Self::from_points(self.points.iter().flat_map(|p| {
+            [*p].into_iter()
+                .chain(Direction::ORTHOGONAL.map(|direction| *p + direction))
+        }))
+    }
+
+    // Add the orthogonal and diagonal neighbors of all points
+    pub fn shake8(&self) -> Self {
+        Self::from_points(self.points.iter().flat_map(|p| {
+            [*p].into_iter()
+                .chain(Direction::ALL.map(|direction| *p + direction))
+        }))
+    }
+
+    // The points inside the window with the given dimensions, whose top left corner is at
+    // 'origin'. Points outside the window are dropped.
+    pub fn to_point_set(&self, origin: IPoint, dimensions: &Dimensions) -> PointSet {
+        PointSet::from_points(
+            dimensions,
+            self.points
+                .iter()
+                .filter_map(|p| Point::try_from(*p - origin).ok())
+                .filter(|p| dimensions.contains(p)),
+        )
+    }
+
+    // The points of a dense set, moved such that its top left corner is at 'origin'
+    pub fn from_point_set(point_set: &PointSet, origin: IPoint) -> Self {
+        Self::from_points(point_set.iter().map(|p| IPoint::from(p) + origin))
+    }
+}
+
+impl FromIterator<IPoint> for SparsePointSet {
+    fn from_iter<T: IntoIterator<Item = IPoint>>(iter: T) -> Self {
+        Self::from_points(iter)
+    }
+}
+
+impl Debug for SparsePointSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ {} }}",
+            self.points()
+                .iter()
+                .map(|IPoint { x, y }| format!("({}, {})", x, y))
+                .join(", ")
+        )
+    }
+}
+
+impl BitAnd for &SparsePointSet {
+    type Output = SparsePointSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.points.intersection(&rhs.points).copied().collect()
+    }
+}
+
+impl BitOr for &SparsePointSet {
+    type Output = SparsePointSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.points.union(&rhs.points).copied().collect()
+    }
+}
+
+impl BitXor for &SparsePointSet {
+    type Output = SparsePointSet;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.points
+            .symmetric_difference(&rhs.points)
+            .copied()
+            .collect()
+    }
+}
+
+impl Sub for &SparsePointSet {
+    type Output = SparsePointSet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.points.difference(&rhs.points).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipoint(x: i64, y: i64) -> IPoint {
+        IPoint { x, y }
+    }
+
+    #[test]
+    fn test_ipoint() {
+        assert_eq!(ipoint(0, 0) + Direction::UpLeft, ipoint(-1, -1));
+        assert_eq!(ipoint(3, -2) - ipoint(1, 1), ipoint(2, -3));
+        assert_eq!(ipoint(3, -2).manhattan_distance(&ipoint(-1, 1)), 7);
+
+        assert_eq!(Point::try_from(ipoint(2, 3)), Ok(Point { x: 2, y: 3 }));
+        assert!(Point::try_from(ipoint(-1, 3)).is_err());
+        assert_eq!(IPoint::from(Point { x: 2, y: 3 }), ipoint(2, 3));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let mut set = SparsePointSet::new();
+        assert_eq!(set.bounding_box(), None);
+
+        set.insert(ipoint(0, 0));
+        set.insert(ipoint(-5, 2));
+        set.insert(ipoint(3, -1));
+        assert_eq!(set.bounding_box(), Some((ipoint(-5, -1), ipoint(3, 2))));
+
+        assert!(set.remove(&ipoint(-5, 2)));
+        assert!(!set.remove(&ipoint(-5, 2)));
+        assert_eq!(set.bounding_box(), Some((ipoint(0, -1), ipoint(3, 0))));
+
+        // the box only shrinks when the last point in a row or column is removed
+        set.insert(ipoint(3, 0));
+        assert!(set.remove(&ipoint(3, -1)));
+        assert_eq!(set.bounding_box(), Some((ipoint(0, 0), ipoint(3, 0))));
+        assert!(set.remove(&ipoint(3, 0)));
+        assert_eq!(set.bounding_box(), Some((ipoint(0, 0), ipoint(0, 0))));
+
+        set.remove(&ipoint(0, 0));
+        assert_eq!(set.bounding_box(), None);
+        assert_eq!(set, SparsePointSet::new());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = SparsePointSet::from_points([ipoint(0, 0), ipoint(1, 0), ipoint(-1, -1)]);
+        let b = SparsePointSet::from_points([ipoint(1, 0), ipoint(2, 2)]);
+
+        assert_eq!((&a & &b).points(), vec![ipoint(1, 0)]);
+        assert_eq!((&a | &b).len(), 4);
+        assert_eq!(
+            (&a ^ &b).points(),
+            vec![ipoint(-1, -1), ipoint(0, 0), ipoint(2, 2)]
+        );
+        assert_eq!((&a - &b).points(), vec![ipoint(-1, -1), ipoint(0, 0)]);
+    }
+
+    #[test]
+    fn test_shift() {
+        let set = SparsePointSet::from_points([ipoint(0, 0), ipoint(1, 0)]);
+
+        assert_eq!(
+            set.shift(Direction::Left).points(),
+            vec![ipoint(-1, 0), ipoint(0, 0)]
+        );
+        assert_eq!(
+            set.translate(-3, 5).points(),
+            vec![ipoint(-3, 5), ipoint(-2, 5)]
+        );
+        assert_eq!(set.shake().len(), 8);
+        assert_eq!(set.shake8().len(), 12);
+        assert_eq!(
+            set.shake().bounding_box(),
+            Some((ipoint(-1, -1), ipoint(2, 1)))
+        );
+    }
+
+    #[test]
+    fn test_point_set_conversion() {
+        let set = SparsePointSet::from_points([ipoint(-2, -2), ipoint(0, 1), ipoint(5, 1)]);
+        let dimensions = Dimensions {
+            width: 4,
+            height: 4,
+        };
+
+        let window = set.to_point_set(ipoint(-2, -2), &dimensions);
+        assert_eq!(
+            window.points(),
+            vec![Point { x: 0, y: 0 }, Point { x: 2, y: 3 }]
+        );
+
+        let back = SparsePointSet::from_point_set(&window, ipoint(-2, -2));
+        assert_eq!(back.points(), vec![ipoint(-2, -2), ipoint(0, 1)]);
+    }
+}
@@ -3,8 +3,7 @@ use std::ops::RangeInclusive;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("../inputs/2025/day02.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2025, 2)
 }
 
 fn main() {
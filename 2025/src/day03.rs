@@ -1,8 +1,7 @@
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("../inputs/2025/day03.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2025, 3)
 }
 
 fn main() {
@@ -2,8 +2,7 @@ use std::cmp::min;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("../inputs/2025/day06.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2025, 6)
 }
 
 fn main() {
@@ -2,8 +2,7 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("../inputs/2025/day07.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2025, 7)
 }
 
 fn main() {
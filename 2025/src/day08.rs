@@ -2,19 +2,18 @@ use std::collections::HashSet;
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("../inputs/2025/day08.txt")
-        .expect("Could not read file")
+    aoc_core::input::read(2025, 8)
 }
 
 fn main() {
-    let data = input();
+    let positions = parse(&input());
 
-    println!("Part 1: {}", part1(&data, 1000));
-    println!("Part 2: {}", part2(&data));
+    println!("Part 1: {}", part1(&positions, 1000));
+    println!("Part 2: {}", part2(&positions));
 }
 
-fn part1(lines: &str, connections: usize) -> usize {
-    connect_boxes(lines, connections).0
+fn part1(positions: &[(i64, i64, i64)], connections: usize) -> usize {
+    connect_boxes(positions, connections).0
         .iter()
         .map(|boxes| boxes.len())
         .sorted_by(|a, b| b.cmp(a))
@@ -22,10 +21,10 @@ fn part1(lines: &str, connections: usize) -> usize {
         .product()
 }
 
-fn part2(lines: &str) -> i64 {
-    let box_count = lines.lines().count();
+fn part2(positions: &[(i64, i64, i64)]) -> i64 {
+    let box_count = positions.len();
 
-    if let Some((last_box_1, last_box_2)) = connect_boxes(lines, box_count * box_count).1 {
+    if let Some((last_box_1, last_box_2)) = connect_boxes(positions, box_count * box_count).1 {
         last_box_1.0 * last_box_2.0
     } else {
         panic!("No boxes found");
@@ -33,13 +32,12 @@ fn part2(lines: &str) -> i64 {
 }
 
 fn connect_boxes(
-    lines: &str,
+    positions: &[(i64, i64, i64)],
     max_connections: usize,
 ) -> (
     Vec<HashSet<usize>>,
     Option<((i64, i64, i64), (i64, i64, i64))>,
 ) {
-    let positions = parse(lines);
     let sorted_squared_distances: Vec<(u64, (usize, usize))> = positions
         .iter()
         .enumerate()
@@ -158,11 +156,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(40, part1(TEST_INPUT, 10));
+        assert_eq!(40, part1(&parse(TEST_INPUT), 10));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(25272, part2(TEST_INPUT));
+        assert_eq!(25272, part2(&parse(TEST_INPUT)));
     }
 }
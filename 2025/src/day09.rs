@@ -1,7 +1,7 @@
 use itertools::Itertools;
 
 fn input() -> String {
-    std::fs::read_to_string("../inputs/2025/day09.txt").expect("Could not read file")
+    aoc_core::input::read(2025, 9)
 }
 
 fn main() {
@@ -0,0 +1,21 @@
+use aoc_core::{day, Day, DEFAULT_VARIANT};
+
+day!(day01, "../../../2023/src/day01.rs");
+day!(day02, "../../../2023/src/day02.rs");
+day!(day03, "../../../2023/src/day03.rs");
+day!(day04, "../../../2023/src/day04.rs");
+day!(day05, "../../../2023/src/day05.rs");
+day!(day06, "../../../2023/src/day06.rs");
+day!(day07, "../../../2023/src/day07.rs");
+day!(day08, "../../../2023/src/day08.rs");
+
+pub const SOLUTIONS: &[Day] = &[
+    Day::new::<day01::Puzzle>(2023, 1, DEFAULT_VARIANT),
+    Day::new::<day02::Puzzle>(2023, 2, DEFAULT_VARIANT),
+    Day::new::<day03::Puzzle>(2023, 3, DEFAULT_VARIANT),
+    Day::new::<day04::Puzzle>(2023, 4, DEFAULT_VARIANT),
+    Day::new::<day05::Puzzle>(2023, 5, DEFAULT_VARIANT),
+    Day::new::<day06::Puzzle>(2023, 6, DEFAULT_VARIANT),
+    Day::new::<day07::Puzzle>(2023, 7, DEFAULT_VARIANT),
+    Day::new::<day08::Puzzle>(2023, 8, DEFAULT_VARIANT),
+];
@@ -0,0 +1,92 @@
+use aoc_core::{day, Day, DEFAULT_VARIANT};
+
+day!(day01, "../../../2024/src/day01.rs");
+day!(day02, "../../../2024/src/day02.rs");
+day!(day03, "../../../2024/src/day03.rs");
+day!(day04, "../../../2024/src/day04.rs");
+day!(day05, "../../../2024/src/day05.rs");
+day!(day06, "../../../2024/src/day06.rs");
+day!(day07, "../../../2024/src/day07.rs");
+day!(day08, "../../../2024/src/day08.rs");
+day!(day09, "../../../2024/src/day09.rs");
+day!(day10, "../../../2024/src/day10.rs");
+day!(day11, "../../../2024/src/day11.rs");
+day!(day12, "../../../2024/src/day12.rs");
+day!(day13, "../../../2024/src/day13.rs", {
+    aoc_core::solution!(Puzzle, part1, part2);
+    aoc_core::solution!(Efficient, part1_efficient, part2);
+});
+day!(day14, "../../../2024/src/day14.rs", {
+    use aoc_core::{Parameters, Solution};
+    use std::fmt::Display;
+
+    pub struct Puzzle;
+
+    impl Solution for Puzzle {
+        type Input = Vec<Robot>;
+
+        const PARAMETERS: &'static [(&'static str, &'static str)] =
+            &[("width", "101"), ("height", "103"), ("seconds", "100")];
+
+        fn parse(data: &str) -> Self::Input {
+            parse(data)
+        }
+
+        fn part1(robots: &Self::Input, parameters: &Parameters) -> impl Display {
+            part1_impl(
+                parameters.get("width"),
+                parameters.get("height"),
+                parameters.get("seconds"),
+                robots,
+            )
+        }
+
+        fn part2(robots: &Self::Input, parameters: &Parameters) -> impl Display {
+            part2_impl(parameters.get("width"), parameters.get("height"), robots)
+        }
+    }
+});
+day!(day15, "../../../2024/src/day15.rs", {
+    aoc_core::solution!(Puzzle, parse -> (Map, Vec<Direction>), part1, part2);
+});
+day!(day18, "../../../2024/src/day18.rs", {
+    aoc_core::solution!(Puzzle, fallen_bytes -> Vec<Point>, part1, part2);
+});
+day!(day19, "../../../2024/src/day19.rs", {
+    aoc_core::solution!(Puzzle, part1, part2);
+    aoc_core::solution!(Naive, part1_naive, part2);
+});
+day!(day20, "../../../2024/src/day20.rs", {
+    aoc_core::solution!(Puzzle, parse -> Map, part1, part2);
+});
+day!(day20_pointset, "../../../2024/src/day20-pointset.rs", {
+    aoc_core::solution!(Puzzle, parse -> Map, part1, part2);
+});
+day!(day22, "../../../2024/src/day22.rs");
+day!(day23, "../../../2024/src/day23.rs");
+
+pub const SOLUTIONS: &[Day] = &[
+    Day::new::<day01::Puzzle>(2024, 1, DEFAULT_VARIANT),
+    Day::new::<day02::Puzzle>(2024, 2, DEFAULT_VARIANT),
+    Day::new::<day03::Puzzle>(2024, 3, DEFAULT_VARIANT),
+    Day::new::<day04::Puzzle>(2024, 4, DEFAULT_VARIANT),
+    Day::new::<day05::Puzzle>(2024, 5, DEFAULT_VARIANT),
+    Day::new::<day06::Puzzle>(2024, 6, DEFAULT_VARIANT),
+    Day::new::<day07::Puzzle>(2024, 7, DEFAULT_VARIANT),
+    Day::new::<day08::Puzzle>(2024, 8, DEFAULT_VARIANT),
+    Day::new::<day09::Puzzle>(2024, 9, DEFAULT_VARIANT),
+    Day::new::<day10::Puzzle>(2024, 10, DEFAULT_VARIANT),
+    Day::new::<day11::Puzzle>(2024, 11, DEFAULT_VARIANT),
+    Day::new::<day12::Puzzle>(2024, 12, DEFAULT_VARIANT),
+    Day::new::<day13::Puzzle>(2024, 13, DEFAULT_VARIANT),
+    Day::new::<day13::Efficient>(2024, 13, "efficient"),
+    Day::new::<day14::Puzzle>(2024, 14, DEFAULT_VARIANT),
+    Day::new::<day15::Puzzle>(2024, 15, DEFAULT_VARIANT),
+    Day::new::<day18::Puzzle>(2024, 18, DEFAULT_VARIANT),
+    Day::new::<day19::Puzzle>(2024, 19, DEFAULT_VARIANT),
+    Day::new::<day19::Naive>(2024, 19, "naive").slow(),
+    Day::new::<day20::Puzzle>(2024, 20, DEFAULT_VARIANT),
+    Day::new::<day20_pointset::Puzzle>(2024, 20, "pointset"),
+    Day::new::<day22::Puzzle>(2024, 22, DEFAULT_VARIANT),
+    Day::new::<day23::Puzzle>(2024, 23, DEFAULT_VARIANT),
+];
@@ -0,0 +1,46 @@
+use aoc_core::{DEFAULT_VARIANT, Day, day};
+
+day!(day01, "../../../2025/src/day01.rs");
+day!(day02, "../../../2025/src/day02.rs");
+day!(day03, "../../../2025/src/day03.rs");
+day!(day04, "../../../2025/src/day04.rs");
+day!(day05, "../../../2025/src/day05.rs");
+day!(day06, "../../../2025/src/day06.rs");
+day!(day07, "../../../2025/src/day07.rs");
+day!(day08, "../../../2025/src/day08.rs", {
+    use aoc_core::{Parameters, Solution};
+    use std::fmt::Display;
+
+    pub struct Puzzle;
+
+    impl Solution for Puzzle {
+        type Input = Vec<(i64, i64, i64)>;
+
+        const PARAMETERS: &'static [(&'static str, &'static str)] = &[("connections", "1000")];
+
+        fn parse(data: &str) -> Vec<(i64, i64, i64)> {
+            parse(data)
+        }
+
+        fn part1(positions: &Vec<(i64, i64, i64)>, parameters: &Parameters) -> impl Display {
+            part1(positions, parameters.get("connections"))
+        }
+
+        fn part2(positions: &Vec<(i64, i64, i64)>, _: &Parameters) -> impl Display {
+            part2(positions)
+        }
+    }
+});
+day!(day09, "../../../2025/src/day09.rs");
+
+pub const SOLUTIONS: &[Day] = &[
+    Day::new::<day01::Puzzle>(2025, 1, DEFAULT_VARIANT),
+    Day::new::<day02::Puzzle>(2025, 2, DEFAULT_VARIANT),
+    Day::new::<day03::Puzzle>(2025, 3, DEFAULT_VARIANT),
+    Day::new::<day04::Puzzle>(2025, 4, DEFAULT_VARIANT),
+    Day::new::<day05::Puzzle>(2025, 5, DEFAULT_VARIANT),
+    Day::new::<day06::Puzzle>(2025, 6, DEFAULT_VARIANT),
+    Day::new::<day07::Puzzle>(2025, 7, DEFAULT_VARIANT),
+    Day::new::<day08::Puzzle>(2025, 8, DEFAULT_VARIANT),
+    Day::new::<day09::Puzzle>(2025, 9, DEFAULT_VARIANT),
+];
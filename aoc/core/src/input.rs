@@ -0,0 +1,203 @@
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// A directory with the inputs in the same layout as the inputs directory of the repository, i.e.,
+// <year>/dayNN.txt. It is searched before the repository. If only a single year is run, the inputs
+// may also be directly in this directory.
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    Stdin(std::io::Error),
+    NotFound {
+        year: u32,
+        day: u32,
+        tried: Vec<(PathBuf, std::io::Error)>,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Stdin(error) => write!(f, "Could not read the input from stdin: {}", error),
+            InputError::NotFound { year, day, tried } => {
+                write!(
+                    f,
+                    "Could not read the input for {} day {}, tried:",
+                    year, day
+                )?;
+                for (path, error) in tried {
+                    write!(f, "\n  {}: {}", path.display(), error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// The directory which contains the inputs of all years
+pub fn repository_inputs() -> PathBuf {
+    let repository = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .unwrap();
+    repository.join("inputs")
+}
+
+// The value of AOC_INPUT_DIR, if it is set
+pub fn input_dir() -> Option<PathBuf> {
+    std::env::var_os(INPUT_DIR_VARIABLE).map(PathBuf::from)
+}
+
+// The files which may contain the input, in the order in which they are tried. An explicitly given
+// file is the only candidate. The file name dayNN.txt does not contain the year, so the inputs
+// directly in the input directory are only tried if a single year is run.
+pub fn candidates(
+    year: u32,
+    day: u32,
+    input: Option<&Path>,
+    input_dir: Option<&Path>,
+    single_year: bool,
+) -> Vec<PathBuf> {
+    if let Some(input) = input {
+        return vec![input.to_path_buf()];
+    }
+
+    let file_name = format!("day{:02}.txt", day);
+    let in_layout = |dir: &Path| dir.join(year.to_string()).join(&file_name);
+
+    match input_dir {
+        Some(dir) if single_year => vec![in_layout(dir), dir.join(&file_name)],
+        Some(dir) => vec![in_layout(dir)],
+        None => vec![],
+    }
+    .into_iter()
+    .chain([in_layout(&repository_inputs())])
+    .collect()
+}
+
+// Reads the input from the given file, from stdin if the file is "-", or from the first candidate
+// which can be read otherwise
+pub fn load(
+    year: u32,
+    day: u32,
+    input: Option<&str>,
+    single_year: bool,
+) -> Result<String, InputError> {
+    if input == Some("-") {
+        let mut data = String::new();
+        std::io::stdin()
+            .read_to_string(&mut data)
+            .map_err(InputError::Stdin)?;
+        return Ok(data);
+    }
+
+    read_first(
+        year,
+        day,
+        candidates(
+            year,
+            day,
+            input.map(Path::new),
+            input_dir().as_deref(),
+            single_year,
+        ),
+    )
+    .map(|(data, _)| data)
+}
+
+// Reads the input from the first of the default candidates which exists, and returns it together
+// with the file it was read from
+pub fn find(
+    year: u32,
+    day: u32,
+    input_dir: Option<&Path>,
+    single_year: bool,
+) -> Result<(String, PathBuf), InputError> {
+    read_first(
+        year,
+        day,
+        candidates(year, day, None, input_dir, single_year),
+    )
+}
+
+fn read_first(
+    year: u32,
+    day: u32,
+    candidates: Vec<PathBuf>,
+) -> Result<(String, PathBuf), InputError> {
+    let mut tried = vec![];
+
+    for path in candidates {
+        match std::fs::read_to_string(&path) {
+            Ok(data) => return Ok((data, path)),
+            Err(error) => tried.push((path, error)),
+        }
+    }
+
+    Err(InputError::NotFound { year, day, tried })
+}
+
+// The input for the day. Panics with all paths which were tried if it cannot be read.
+pub fn read(year: u32, day: u32) -> String {
+    load(year, day, None, true).unwrap_or_else(|error| panic!("{}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let in_repository = repository_inputs().join("2024").join("day05.txt");
+
+        assert_eq!(
+            candidates(2024, 5, None, None, true),
+            vec![in_repository.clone()]
+        );
+        assert_eq!(
+            candidates(2024, 5, None, Some(Path::new("/aoc")), true),
+            vec![
+                PathBuf::from("/aoc/2024/day05.txt"),
+                PathBuf::from("/aoc/day05.txt"),
+                in_repository.clone()
+            ]
+        );
+
+        // the input of another year must not be used
+        assert_eq!(
+            candidates(2024, 5, None, Some(Path::new("/aoc")), false),
+            vec![PathBuf::from("/aoc/2024/day05.txt"), in_repository]
+        );
+
+        assert_eq!(
+            candidates(
+                2024,
+                5,
+                Some(Path::new("x.txt")),
+                Some(Path::new("/aoc")),
+                false
+            ),
+            vec![PathBuf::from("x.txt")]
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let this_file = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.rs");
+        assert_eq!(
+            load(2024, 1, Some(this_file), true).unwrap(),
+            include_str!("input.rs")
+        );
+
+        let error = load(2024, 1, Some("no/such/file.txt"), true).unwrap_err();
+        let message = error.to_string();
+        let lines = message.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Could not read the input for 2024 day 1, tried:");
+        assert!(lines[1].starts_with("  no/such/file.txt: "));
+        assert_eq!(lines.len(), 2);
+    }
+}
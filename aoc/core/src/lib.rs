@@ -0,0 +1,291 @@
+pub mod input;
+
+use std::any::Any;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// The solution of one puzzle. The input is parsed once and then shared by both parts.
+//
+// Some puzzles have parameters, e.g., the size of the grid, which differ between the example in
+// the puzzle description and the real input. They are declared with their values for the real
+// input in PARAMETERS, and can be overridden by name.
+pub trait Solution {
+    type Input: 'static;
+
+    const PARAMETERS: &'static [(&'static str, &'static str)] = &[];
+
+    fn parse(data: &str) -> Self::Input;
+
+    fn part1(input: &Self::Input, parameters: &Parameters) -> impl Display;
+
+    fn part2(input: &Self::Input, parameters: &Parameters) -> impl Display;
+}
+
+// The values of the parameters of a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameters {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Parameters {
+    // The default values, with the given values replaced. Unknown names are an error.
+    pub fn new(
+        defaults: &[(&'static str, &'static str)],
+        overrides: &[(String, String)],
+    ) -> Result<Self, String> {
+        let mut values = defaults
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect::<Vec<_>>();
+
+        for (name, value) in overrides {
+            match values.iter_mut().find(|(known, _)| known == name) {
+                Some((_, old_value)) => *old_value = value.clone(),
+                None => return Err(format!("Unknown parameter '{}'", name)),
+            }
+        }
+
+        Ok(Self { values })
+    }
+
+    pub fn defaults(defaults: &[(&'static str, &'static str)]) -> Self {
+        Self::new(defaults, &[]).unwrap()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> + '_ {
+        self.values
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+    }
+
+    // Panics if the solution did not declare the parameter, or if the value cannot be parsed
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        let (_, value) = self
+            .values
+            .iter()
+            .find(|(known, _)| *known == name)
+            .unwrap_or_else(|| panic!("Unknown parameter '{}'", name));
+
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid value '{}' for parameter '{}'", value, name))
+    }
+}
+
+// A registered solution, with the input type erased such that solutions of different puzzles can
+// be stored together
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub variant: &'static str,
+    // Too slow to be run for all variants, e.g., a naive implementation which is kept for comparison
+    pub slow: bool,
+    pub parameters: &'static [(&'static str, &'static str)],
+    pub parse: fn(&str) -> Box<dyn Any>,
+    pub part1: fn(&dyn Any, &Parameters) -> String,
+    pub part2: fn(&dyn Any, &Parameters) -> String,
+}
+
+pub const DEFAULT_VARIANT: &str = "default";
+
+fn parse<S: Solution>(data: &str) -> Box<dyn Any> {
+    Box::new(S::parse(data))
+}
+
+fn input<S: Solution>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref()
+        .expect("The input was parsed by a different solution")
+}
+
+fn part1<S: Solution>(data: &dyn Any, parameters: &Parameters) -> String {
+    S::part1(input::<S>(data), parameters).to_string()
+}
+
+fn part2<S: Solution>(data: &dyn Any, parameters: &Parameters) -> String {
+    S::part2(input::<S>(data), parameters).to_string()
+}
+
+impl Day {
+    pub const fn new<S: Solution>(year: u32, day: u32, variant: &'static str) -> Self {
+        Self {
+            year,
+            day,
+            variant,
+            slow: false,
+            parameters: S::PARAMETERS,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+        }
+    }
+
+    pub const fn slow(self) -> Self {
+        Self { slow: true, ..self }
+    }
+
+    pub fn default_parameters(&self) -> Parameters {
+        Parameters::defaults(self.parameters)
+    }
+
+    // Parses the input and solves both parts
+    pub fn solve(&self, data: &str, parameters: &Parameters) -> (String, String) {
+        let input = (self.parse)(data);
+        (
+            (self.part1)(input.as_ref(), parameters),
+            (self.part2)(input.as_ref(), parameters),
+        )
+    }
+}
+
+// All registered solutions, in groups which are usually the solutions of one year
+#[derive(Debug, Clone, Copy)]
+pub struct Registry {
+    groups: &'static [&'static [Day]],
+}
+
+impl Registry {
+    pub const fn new(groups: &'static [&'static [Day]]) -> Self {
+        Self { groups }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static Day> {
+        self.groups.iter().flat_map(|group| group.iter())
+    }
+
+    pub fn get(&self, year: u32, day: u32, variant: &str) -> Option<&'static Day> {
+        self.iter().find(|solution| {
+            (solution.year, solution.day, solution.variant) == (year, day, variant)
+        })
+    }
+
+    // All variants of the solution for a day
+    pub fn variants(&self, year: u32, day: u32) -> impl Iterator<Item = &'static Day> {
+        self.iter()
+            .filter(move |solution| (solution.year, solution.day) == (year, day))
+    }
+}
+
+// Implements Solution for a puzzle. With a parse function and the type it returns, part1 and part2
+// take a reference to the parsed input, e.g.
+//
+//     solution!(Puzzle, parse -> Map, part1, part2);
+//
+// Without it, part1 and part2 take the unparsed input.
+#[macro_export]
+macro_rules! solution {
+    ($name:ident, $part1:ident, $part2:ident) => {
+        $crate::solution!($name, str::to_string -> String, $part1, $part2);
+    };
+    ($name:ident, $($parse:ident)::+ -> $input:ty, $part1:ident, $part2:ident) => {
+        pub struct $name;
+
+        impl $crate::Solution for $name {
+            type Input = $input;
+
+            fn parse(data: &str) -> $input {
+                $($parse)::+(data)
+            }
+
+            fn part1(input: &$input, _: &$crate::Parameters) -> impl std::fmt::Display {
+                $part1(input)
+            }
+
+            fn part2(input: &$input, _: &$crate::Parameters) -> impl std::fmt::Display {
+                $part2(input)
+            }
+        }
+    };
+}
+
+// Includes the source of a day's binary into a module. The include path is relative to the file
+// which invokes the macro. Warnings in the included code are reported when building the year's own
+// package.
+//
+// By default, the module gets a Solution called Puzzle, which uses the part1 and part2 functions.
+// Other solutions can be added to the module explicitly, e.g., with the solution! macro.
+#[macro_export]
+macro_rules! day {
+    ($module:ident, $path:literal) => {
+        $crate::day!($module, $path, {
+            $crate::solution!(Puzzle, part1, part2);
+        });
+    };
+    ($module:ident, $path:literal, { $($items:item)* }) => {
+        #[allow(warnings, clippy::all)]
+        pub mod $module {
+            include!($path);
+
+            $($items)*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        const PARAMETERS: &'static [(&'static str, &'static str)] = &[("factor", "2")];
+
+        fn parse(data: &str) -> Vec<i32> {
+            data.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part1(input: &Vec<i32>, _: &Parameters) -> impl Display {
+            input.iter().sum::<i32>()
+        }
+
+        fn part2(input: &Vec<i32>, parameters: &Parameters) -> impl Display {
+            input.iter().sum::<i32>() * parameters.get::<i32>("factor")
+        }
+    }
+
+    const SOLUTIONS: &[Day] = &[
+        Day::new::<Sum>(2024, 1, DEFAULT_VARIANT),
+        Day::new::<Sum>(2024, 1, "other").slow(),
+    ];
+
+    #[test]
+    fn test_solve() {
+        let day = Day::new::<Sum>(2024, 1, DEFAULT_VARIANT);
+        assert_eq!(
+            day.solve("1\n2\n3", &day.default_parameters()),
+            ("6".to_string(), "12".to_string())
+        );
+
+        let parameters =
+            Parameters::new(day.parameters, &[("factor".to_string(), "3".to_string())]).unwrap();
+        assert_eq!(parameters.iter().collect::<Vec<_>>(), vec![("factor", "3")]);
+        assert_eq!(day.solve("1\n2\n3", &parameters).1, "18");
+    }
+
+    #[test]
+    fn test_unknown_parameter() {
+        assert!(
+            Parameters::new(&[("factor", "2")], &[("f".to_string(), "3".to_string())]).is_err()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid value 'x' for parameter 'factor'")]
+    fn test_invalid_parameter() {
+        Parameters::defaults(&[("factor", "x")]).get::<i32>("factor");
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = Registry::new(&[SOLUTIONS, &[]]);
+        assert_eq!(registry.iter().count(), 2);
+        assert_eq!(registry.get(2024, 1, "other").unwrap().variant, "other");
+        assert!(registry.get(2024, 2, DEFAULT_VARIANT).is_none());
+        assert_eq!(registry.variants(2024, 1).count(), 2);
+        assert!(!registry.get(2024, 1, DEFAULT_VARIANT).unwrap().slow);
+        assert!(registry.get(2024, 1, "other").unwrap().slow);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = "answers.toml";
+
+// The correct answers for the real inputs of one year. They are stored next to the inputs, with
+// one table per day:
+//
+//     [day18]
+//     part1 = 308
+//     part2 = "46,28"
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u32, u8), String>,
+}
+
+impl Answers {
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        let table = toml
+            .parse::<toml::Table>()
+            .map_err(|error| error.to_string())?;
+        let mut answers = HashMap::new();
+
+        for (key, parts) in &table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| format!("Expected a table like [day01], got [{}]", key))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("Expected a table for [{}]", key))?;
+
+            for (key, answer) in parts {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(format!("Unexpected key {} for day {}", key, day)),
+                };
+                let answer = match answer {
+                    toml::Value::String(answer) => answer.clone(),
+                    toml::Value::Integer(answer) => answer.to_string(),
+                    _ => return Err(format!("Unexpected answer {} for day {}", answer, day)),
+                };
+
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    // The answers file which belongs to an input, i.e., the one in the same directory
+    pub fn path_for(input: &Path) -> PathBuf {
+        input.with_file_name(FILE_NAME)
+    }
+
+    // The answers in the given file, or no answers if it does not exist
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match path.exists() {
+            true => Self::read(path),
+            false => Ok(Self::default()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        std::fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|toml| Self::parse(&toml))
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))
+    }
+
+    pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("[day18]\npart1 = 308\npart2 = \"46,28\"\n\n[day20]\npart1 = 1497")
+                .unwrap();
+
+        assert_eq!(answers.get(18, 1), Some("308"));
+        assert_eq!(answers.get(18, 2), Some("46,28"));
+        assert_eq!(answers.get(20, 1), Some("1497"));
+        assert_eq!(answers.get(20, 2), None);
+        assert_eq!(answers.get(1, 1), None);
+
+        assert!(Answers::parse("[day18]\npart3 = 1").is_err());
+        assert!(Answers::parse("[eighteen]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day18]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("[day18\n").is_err());
+    }
+
+    #[test]
+    fn test_flat_input_dir() {
+        // the input and the answers are directly in AOC_INPUT_DIR, without a directory per year,
+        // which is only possible if a single year is run
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day05.txt"), "input").unwrap();
+        std::fs::write(dir.join(FILE_NAME), "[day05]\npart1 = 143").unwrap();
+
+        let (data, input) = aoc_core::input::find(2024, 5, Some(&dir), true).unwrap();
+        let answers = Answers::load(&Answers::path_for(&input)).unwrap();
+        assert_eq!(data, "input");
+        assert_eq!(answers.get(5, 1), Some("143"));
+
+        let flat = dir.join("day05.txt");
+        assert!(!matches!(
+            aoc_core::input::find(2023, 5, Some(&dir), false),
+            Ok((_, path)) if path == flat
+        ));
+
+        // the answers of the repository do not belong to the input
+        std::fs::remove_file(dir.join(FILE_NAME)).unwrap();
+        assert_eq!(
+            Answers::load(&Answers::path_for(&input)).unwrap(),
+            Answers::default()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_answers_of_the_repository() {
+        for year in [2023, 2024, 2025] {
+            let path = aoc_core::input::repository_inputs()
+                .join(year.to_string())
+                .join(FILE_NAME);
+            Answers::read(&path).unwrap();
+        }
+    }
+}
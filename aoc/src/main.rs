@@ -0,0 +1,454 @@
+mod answers;
+mod timing;
+mod verify;
+
+use aoc_core::{Day, Parameters, Registry, DEFAULT_VARIANT};
+use clap::{Parser, Subcommand};
+use itertools::Itertools;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code solutions of all years")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solution of one day, or of all days of a year
+    Run {
+        #[arg(long)]
+        year: u32,
+
+        /// Runs all days of the year if omitted
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Runs both parts if omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Alternative implementation, e.g., "pointset". Days without it use the default one.
+        #[arg(long, default_value = DEFAULT_VARIANT)]
+        variant: String,
+
+        /// Input file, or "-" for stdin. By default, the input is looked for in $AOC_INPUT_DIR, with
+        /// or without a directory for the year, and in the inputs directory of the repository.
+        #[arg(long, requires = "day")]
+        input: Option<String>,
+
+        /// Overrides a parameter of the puzzle, e.g., "connections=10"
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_parameter, requires = "day")]
+        parameters: Vec<(String, String)>,
+    },
+
+    /// Checks the answers for the real inputs against the answers.toml files next to the inputs
+    Verify {
+        /// Verifies all years if omitted
+        #[arg(long)]
+        year: Option<u32>,
+
+        #[arg(long, requires = "year")]
+        day: Option<u32>,
+
+        /// Also verifies the alternative implementations
+        #[arg(long)]
+        all_variants: bool,
+
+        /// Also verifies the slow variants, which are skipped by --all-variants otherwise
+        #[arg(long, requires = "all_variants")]
+        include_slow: bool,
+    },
+
+    /// Measures how long parsing and solving the parts take for the real inputs
+    Time {
+        /// Measures all years if omitted
+        #[arg(long)]
+        year: Option<u32>,
+
+        #[arg(long, requires = "year")]
+        day: Option<u32>,
+
+        /// Also measures the alternative implementations
+        #[arg(long)]
+        all_variants: bool,
+
+        /// Also measures the slow variants, which are skipped by --all-variants otherwise
+        #[arg(long, requires = "all_variants")]
+        include_slow: bool,
+
+        /// Number of runs before the measurement
+        #[arg(long, default_value_t = 0)]
+        warmup: usize,
+
+        /// Number of measured runs
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        repeat: u64,
+
+        /// Writes all measurements to a .json or .csv file
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+
+    /// Lists all solutions and their variants
+    List {
+        #[arg(long)]
+        year: Option<u32>,
+    },
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run {
+            year,
+            day,
+            part,
+            variant,
+            input,
+            parameters,
+        } => run(year, day, part, &variant, input.as_deref(), &parameters),
+        Command::Verify {
+            year,
+            day,
+            all_variants,
+            include_slow,
+        } => matching(year, day, all_variants, include_slow)
+            .and_then(|solutions| verify::verify(&solutions, year.is_some())),
+        Command::Time {
+            year,
+            day,
+            all_variants,
+            include_slow,
+            warmup,
+            repeat,
+            report,
+        } => matching(year, day, all_variants, include_slow).and_then(|solutions| {
+            time(
+                &solutions,
+                year.is_some(),
+                warmup,
+                repeat as usize,
+                report.as_deref(),
+            )
+        }),
+        Command::List { year } => {
+            list(year);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+const REGISTRY: Registry =
+    Registry::new(&[aoc2023::SOLUTIONS, aoc2024::SOLUTIONS, aoc2025::SOLUTIONS]);
+
+fn parse_parameter(parameter: &str) -> Result<(String, String), String> {
+    parameter
+        .split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Expected NAME=VALUE, got '{}'", parameter))
+}
+
+// The solutions to run, one per day. Each day uses the requested variant if it exists, and the
+// default variant otherwise.
+fn select(
+    registry: &Registry,
+    year: u32,
+    day: Option<u32>,
+    variant: &str,
+) -> Result<Vec<&'static Day>, String> {
+    let candidates = registry
+        .iter()
+        .filter(|solution| solution.year == year && day.is_none_or(|day| solution.day == day))
+        .collect_vec();
+
+    if candidates.is_empty() {
+        return Err(match day {
+            Some(day) => format!("No solution for {} day {}", year, day),
+            None => format!("No solutions for {}", year),
+        });
+    }
+
+    if !candidates
+        .iter()
+        .any(|solution| solution.variant == variant)
+    {
+        let variants = candidates
+            .iter()
+            .map(|solution| solution.variant)
+            .unique()
+            .join(", ");
+        return Err(format!(
+            "No variant '{}', available variants: {}",
+            variant, variants
+        ));
+    }
+
+    Ok(candidates
+        .into_iter()
+        .into_group_map_by(|solution| solution.day)
+        .into_iter()
+        .sorted_by_key(|(day, _)| *day)
+        .filter_map(|(_, variants)| {
+            variants
+                .iter()
+                .find(|solution| solution.variant == variant)
+                .or_else(|| {
+                    variants
+                        .iter()
+                        .find(|solution| solution.variant == DEFAULT_VARIANT)
+                })
+                .copied()
+        })
+        .collect())
+}
+
+// The solutions of all days which match, optionally with all variants. The slow variants are only
+// included on request.
+fn matching(
+    year: Option<u32>,
+    day: Option<u32>,
+    all_variants: bool,
+    include_slow: bool,
+) -> Result<Vec<&'static Day>, String> {
+    let solutions = REGISTRY
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.year == year))
+        .filter(|solution| day.is_none_or(|day| solution.day == day))
+        .filter(|solution| all_variants || solution.variant == DEFAULT_VARIANT)
+        .filter(|solution| include_slow || !solution.slow)
+        .collect_vec();
+
+    match solutions.is_empty() {
+        true => Err("No matching solutions".to_string()),
+        false => Ok(solutions),
+    }
+}
+
+fn run(
+    year: u32,
+    day: Option<u32>,
+    part: Option<u8>,
+    variant: &str,
+    input: Option<&str>,
+    parameters: &[(String, String)],
+) -> Result<(), String> {
+    let selected = select(&REGISTRY, year, day, variant)?;
+    let mut failed = 0;
+
+    for solution in &selected {
+        let parameters = Parameters::new(solution.parameters, parameters)?;
+
+        if day.is_none() {
+            match solution.variant {
+                DEFAULT_VARIANT => println!("Day {}", solution.day),
+                variant => println!("Day {} ({})", solution.day, variant),
+            }
+        }
+
+        let data = match aoc_core::input::load(year, solution.day, input, true) {
+            Ok(data) => data,
+            Err(error) => {
+                eprintln!("Error: {}", error);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let input = (solution.parse)(&data);
+        if part != Some(2) {
+            println!("Part 1: {}", (solution.part1)(input.as_ref(), &parameters));
+        }
+        if part != Some(1) {
+            println!("Part 2: {}", (solution.part2)(input.as_ref(), &parameters));
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} of {} days could not be run",
+            failed,
+            selected.len()
+        )),
+    }
+}
+
+// Days without an input are skipped. Days which panic are reported as failed, but the other days
+// are still measured. The inputs directly in AOC_INPUT_DIR are only used for a single year.
+fn time(
+    solutions: &[&'static Day],
+    single_year: bool,
+    warmup: usize,
+    repetitions: usize,
+    report: Option<&Path>,
+) -> Result<(), String> {
+    let mut measurements = vec![];
+    let mut failed = 0;
+
+    // The panics are reported with the day
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    for solution in solutions {
+        match aoc_core::input::load(solution.year, solution.day, None, single_year) {
+            Ok(data) => match timing::measure(solution, &data, warmup, repetitions) {
+                Ok(solution_measurements) => measurements.extend(solution_measurements),
+                Err(message) => {
+                    eprintln!(
+                        "{} day {} ({}) panicked: {}",
+                        solution.year, solution.day, solution.variant, message
+                    );
+                    failed += 1;
+                }
+            },
+            Err(_) => eprintln!("No input for {} day {}", solution.year, solution.day),
+        }
+    }
+    std::panic::set_hook(default_hook);
+
+    if measurements.is_empty() {
+        return Err("No solution could be run".to_string());
+    }
+
+    timing::print_summary(&measurements, repetitions);
+
+    if let Some(path) = report {
+        timing::write_report(path, &measurements)?;
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} days panicked", failed)),
+    }
+}
+
+fn list(year: Option<u32>) {
+    for solution in REGISTRY
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.year == year))
+    {
+        let details = solution
+            .slow
+            .then(|| "(slow)".to_string())
+            .into_iter()
+            .chain(
+                solution
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value)),
+            )
+            .join(" ");
+
+        let line = format!(
+            "{} day {:2}  {:10} {}",
+            solution.year, solution.day, solution.variant, details
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        // each variant is registered only once
+        assert!(REGISTRY
+            .iter()
+            .map(|solution| (solution.year, solution.day, solution.variant))
+            .all_unique());
+
+        // and there is a default variant for each day
+        for solution in REGISTRY.iter() {
+            assert!(REGISTRY
+                .get(solution.year, solution.day, DEFAULT_VARIANT)
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let days = |selected: Vec<&Day>| {
+            selected
+                .iter()
+                .map(|solution| (solution.day, solution.variant))
+                .collect_vec()
+        };
+
+        assert_eq!(
+            days(select(&REGISTRY, 2024, Some(20), "default").unwrap()),
+            vec![(20, "default")]
+        );
+        assert_eq!(
+            days(select(&REGISTRY, 2024, Some(20), "pointset").unwrap()),
+            vec![(20, "pointset")]
+        );
+        assert!(select(&REGISTRY, 2024, Some(15), "pointset").is_err());
+        assert!(select(&REGISTRY, 2024, Some(16), "default").is_err());
+        assert!(select(&REGISTRY, 2026, None, "default").is_err());
+
+        // for a whole year, days without the variant fall back to the default one
+        let year = days(select(&REGISTRY, 2024, None, "pointset").unwrap());
+        assert_eq!(year.len(), 20);
+        assert_eq!(year[0], (1, "default"));
+        assert!(year.contains(&(20, "pointset")));
+        assert!(!year.contains(&(20, "default")));
+    }
+
+    #[test]
+    fn test_matching() {
+        let variants = |all_variants, include_slow| {
+            matching(Some(2024), Some(19), all_variants, include_slow)
+                .unwrap()
+                .iter()
+                .map(|solution| solution.variant)
+                .collect_vec()
+        };
+
+        assert_eq!(variants(false, false), vec![DEFAULT_VARIANT]);
+        assert_eq!(variants(true, false), vec![DEFAULT_VARIANT]);
+        assert_eq!(variants(true, true), vec![DEFAULT_VARIANT, "naive"]);
+        assert!(matching(Some(2024), Some(16), true, true).is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        let day01 = REGISTRY.get(2024, 1, DEFAULT_VARIANT).unwrap();
+        assert_eq!(
+            day01.solve(
+                "3   4\n4   3\n2   5\n1   3\n3   9\n3   3",
+                &day01.default_parameters()
+            ),
+            ("11".to_string(), "31".to_string())
+        );
+
+        // the example of 2024 day 14 is on a smaller grid
+        let day14 = REGISTRY.get(2024, 14, DEFAULT_VARIANT).unwrap();
+        let example = "p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\np=0,0 v=1,3\n\
+                       p=3,0 v=-2,-2\np=7,6 v=-1,-3\np=3,0 v=-1,-2\np=9,3 v=2,3\np=7,3 v=-1,2\n\
+                       p=2,4 v=2,-3\np=9,5 v=-3,-3";
+        let parameters = Parameters::new(
+            day14.parameters,
+            &[
+                parse_parameter("width=11").unwrap(),
+                parse_parameter("height=7").unwrap(),
+            ],
+        )
+        .unwrap();
+        let input = (day14.parse)(example);
+        assert_eq!((day14.part1)(input.as_ref(), &parameters), "12");
+
+        assert!(Parameters::new(day14.parameters, &[parse_parameter("depth=3").unwrap()]).is_err());
+        assert!(parse_parameter("width").is_err());
+    }
+}
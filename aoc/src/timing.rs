@@ -0,0 +1,352 @@
+use crate::verify::panic_message;
+use aoc_core::Day;
+use itertools::Itertools;
+use serde::Serialize;
+use std::io::Write;
+use std::panic::catch_unwind;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+// The durations of one phase of a solution in all repetitions
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub solution: &'static Day,
+    pub phase: Phase,
+    pub answer: Option<String>,
+    pub samples: Vec<Duration>,
+}
+
+impl Measurement {
+    pub fn min(&self) -> Duration {
+        self.samples.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let sorted = self.samples.iter().copied().sorted().collect_vec();
+        match sorted.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 1 => sorted[n / 2],
+            n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            n => self.samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+// Runs the solution warmup + repetitions times, and records the durations of the last repetitions.
+// The input is parsed again in each repetition. If the solution panics, the panic message is
+// returned instead.
+pub fn measure(
+    solution: &'static Day,
+    data: &str,
+    warmup: usize,
+    repetitions: usize,
+) -> Result<[Measurement; 3], String> {
+    catch_unwind(|| measure_phases(solution, data, warmup, repetitions)).map_err(panic_message)
+}
+
+fn measure_phases(
+    solution: &'static Day,
+    data: &str,
+    warmup: usize,
+    repetitions: usize,
+) -> [Measurement; 3] {
+    let parameters = solution.default_parameters();
+    let mut measurements = Phase::ALL.map(|phase| Measurement {
+        solution,
+        phase,
+        answer: None,
+        samples: vec![],
+    });
+
+    for repetition in 0..warmup + repetitions {
+        let (input, parse) = timed(|| (solution.parse)(data));
+        let (part1, part1_duration) = timed(|| (solution.part1)(input.as_ref(), &parameters));
+        let (part2, part2_duration) = timed(|| (solution.part2)(input.as_ref(), &parameters));
+
+        if repetition >= warmup {
+            let [parse_measurement, part1_measurement, part2_measurement] = &mut measurements;
+            parse_measurement.samples.push(parse);
+            part1_measurement.samples.push(part1_duration);
+            part1_measurement.answer = Some(part1);
+            part2_measurement.samples.push(part2_duration);
+            part2_measurement.answer = Some(part2);
+        }
+    }
+
+    measurements
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+
+    if seconds >= 1.0 {
+        format!("{:.2} s", seconds)
+    } else if seconds >= 1e-3 {
+        format!("{:.2} ms", seconds * 1e3)
+    } else {
+        format!("{:.2} µs", seconds * 1e6)
+    }
+}
+
+// One row per solution with the median durations of the phases
+pub fn print_summary(measurements: &[Measurement], repetitions: usize) {
+    println!(
+        "{:4} {:>3}  {:10} {:>10} {:>10} {:>10} {:>10}",
+        "Year", "Day", "Variant", "Parse", "Part 1", "Part 2", "Total"
+    );
+
+    for phases in measurements.chunks(Phase::ALL.len()) {
+        let solution = phases[0].solution;
+        let medians = phases.iter().map(Measurement::median).collect_vec();
+
+        println!(
+            "{:4} {:3}  {:10} {:>10} {:>10} {:>10} {:>10}",
+            solution.year,
+            solution.day,
+            solution.variant,
+            format_duration(medians[0]),
+            format_duration(medians[1]),
+            format_duration(medians[2]),
+            format_duration(medians.iter().sum())
+        );
+    }
+
+    println!("\nMedian of {} runs", repetitions);
+}
+
+// One row of the report, with the statistics of one measurement
+#[derive(Debug, Serialize)]
+struct Row<'a> {
+    year: u32,
+    day: u32,
+    variant: &'a str,
+    phase: &'static str,
+    answer: Option<&'a str>,
+    runs: usize,
+    min_ns: u128,
+    median_ns: u128,
+    mean_ns: u128,
+}
+
+impl<'a> Row<'a> {
+    const FIELDS: [&'static str; 9] = [
+        "year",
+        "day",
+        "variant",
+        "phase",
+        "answer",
+        "runs",
+        "min_ns",
+        "median_ns",
+        "mean_ns",
+    ];
+
+    fn new(measurement: &'a Measurement) -> Self {
+        Self {
+            year: measurement.solution.year,
+            day: measurement.solution.day,
+            variant: measurement.solution.variant,
+            phase: measurement.phase.name(),
+            answer: measurement.answer.as_deref(),
+            runs: measurement.samples.len(),
+            min_ns: measurement.min().as_nanos(),
+            median_ns: measurement.median().as_nanos(),
+            mean_ns: measurement.mean().as_nanos(),
+        }
+    }
+
+    // The values in the order of FIELDS
+    fn csv_record(&self) -> [String; 9] {
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            csv_field(self.variant),
+            csv_field(self.phase),
+            csv_field(self.answer.unwrap_or("")),
+            self.runs.to_string(),
+            self.min_ns.to_string(),
+            self.median_ns.to_string(),
+            self.mean_ns.to_string(),
+        ]
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// An array with one object per measurement, which contains the same fields as the CSV report
+pub fn write_json(writer: &mut impl Write, measurements: &[Measurement]) -> std::io::Result<()> {
+    let rows = measurements.iter().map(Row::new).collect_vec();
+    serde_json::to_writer_pretty(&mut *writer, &rows)?;
+    writeln!(writer)
+}
+
+pub fn write_csv(writer: &mut impl Write, measurements: &[Measurement]) -> std::io::Result<()> {
+    writeln!(writer, "{}", Row::FIELDS.join(","))?;
+
+    for measurement in measurements {
+        writeln!(writer, "{}", Row::new(measurement).csv_record().join(","))?;
+    }
+
+    Ok(())
+}
+
+// Writes a JSON or CSV report, depending on the extension of the path
+pub fn write_report(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let extension = path.extension().and_then(|extension| extension.to_str());
+    if !matches!(extension, Some("json" | "csv")) {
+        return Err(format!(
+            "Cannot write {}, the report must be a .json or .csv file",
+            path.display()
+        ));
+    }
+
+    let mut file = std::io::BufWriter::new(
+        std::fs::File::create(path)
+            .map_err(|error| format!("Could not create {}: {}", path.display(), error))?,
+    );
+
+    match extension {
+        Some("json") => write_json(&mut file, measurements),
+        _ => write_csv(&mut file, measurements),
+    }
+    .and_then(|()| file.flush())
+    .map_err(|error| format!("Could not write {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day18() -> &'static Day {
+        aoc2024::SOLUTIONS
+            .iter()
+            .find(|solution| solution.day == 18)
+            .unwrap()
+    }
+
+    fn measurements() -> Vec<Measurement> {
+        let milliseconds =
+            |values: &[u64]| values.iter().map(|ms| Duration::from_millis(*ms)).collect();
+
+        vec![
+            Measurement {
+                solution: day18(),
+                phase: Phase::Parse,
+                answer: None,
+                samples: milliseconds(&[3, 1, 2]),
+            },
+            Measurement {
+                solution: day18(),
+                phase: Phase::Part2,
+                answer: Some("46,28".to_string()),
+                samples: milliseconds(&[4, 1, 2, 3]),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_statistics() {
+        let measurements = measurements();
+
+        assert_eq!(measurements[0].min(), Duration::from_millis(1));
+        assert_eq!(measurements[0].median(), Duration::from_millis(2));
+        assert_eq!(measurements[0].mean(), Duration::from_millis(2));
+        assert_eq!(measurements[1].median(), Duration::from_micros(2500));
+        assert_eq!(measurements[1].mean(), Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn test_measure() {
+        let example = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n5,5\n2,5\n\
+                       6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0";
+        let [parse, part1, part2] = measure(day18(), example, 1, 3).unwrap();
+
+        assert_eq!(parse.phase, Phase::Parse);
+        assert_eq!(parse.samples.len(), 3);
+        assert_eq!(parse.answer, None);
+        assert_eq!(part1.answer.as_deref(), Some("22"));
+        assert_eq!(part2.answer.as_deref(), Some("6,1"));
+    }
+
+    #[test]
+    fn test_measure_panic() {
+        // the input is not a list of coordinates
+        assert!(measure(day18(), "5,4\nx", 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_reports() {
+        let mut csv = vec![];
+        write_csv(&mut csv, &measurements()).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "year,day,variant,phase,answer,runs,min_ns,median_ns,mean_ns\n\
+             2024,18,default,parse,,3,1000000,2000000,2000000\n\
+             2024,18,default,part2,\"46,28\",4,1000000,2500000,2500000\n"
+        );
+
+        let mut json = vec![];
+        write_json(&mut json, &measurements()).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!([
+                {
+                    "year": 2024, "day": 18, "variant": "default", "phase": "parse",
+                    "answer": null, "runs": 3, "min_ns": 1000000, "median_ns": 2000000,
+                    "mean_ns": 2000000
+                },
+                {
+                    "year": 2024, "day": 18, "variant": "default", "phase": "part2",
+                    "answer": "46,28", "runs": 4, "min_ns": 1000000, "median_ns": 2500000,
+                    "mean_ns": 2500000
+                }
+            ])
+        );
+
+        assert!(write_report(Path::new("report.txt"), &measurements()).is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(12)), "12.00 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+}
@@ -0,0 +1,223 @@
+use crate::answers::Answers;
+use aoc_core::input::input_dir;
+use aoc_core::Day;
+use itertools::Itertools;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Answer(String),
+    NoInput,
+    Panicked(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+#[derive(Debug)]
+struct Check {
+    solution: &'static Day,
+    part: u8,
+    expected: Option<String>,
+    outcome: Outcome,
+}
+
+impl Check {
+    fn status(&self) -> Status {
+        match (&self.outcome, &self.expected) {
+            (Outcome::NoInput, _) => Status::Missing,
+            (Outcome::Panicked(_), _) => Status::Fail,
+            (Outcome::Answer(_), None) => Status::Missing,
+            (Outcome::Answer(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Outcome::Answer(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+// Solves both parts. A panic in one part does not prevent solving the other one.
+fn solve(solution: &Day, data: &str) -> [Outcome; 2] {
+    let parameters = solution.default_parameters();
+
+    let input = match catch_unwind(|| (solution.parse)(data)) {
+        Ok(input) => input,
+        Err(payload) => {
+            let outcome = Outcome::Panicked(panic_message(payload));
+            return [outcome.clone(), outcome];
+        }
+    };
+
+    [solution.part1, solution.part2].map(|part| {
+        catch_unwind(AssertUnwindSafe(|| part(input.as_ref(), &parameters))).map_or_else(
+            |payload| Outcome::Panicked(panic_message(payload)),
+            Outcome::Answer,
+        )
+    })
+}
+
+// The answers are read from the answers file next to the input, so that the inputs and answers of
+// AOC_INPUT_DIR are not compared with the answers of the repository
+fn check(
+    solution: &'static Day,
+    single_year: bool,
+    answers: &mut BTreeMap<PathBuf, Answers>,
+) -> Result<[Check; 2], String> {
+    let input = aoc_core::input::find(
+        solution.year,
+        solution.day,
+        input_dir().as_deref(),
+        single_year,
+    );
+    let (outcomes, answers) = match input {
+        Ok((data, input)) => {
+            let path = Answers::path_for(&input);
+            if !answers.contains_key(&path) {
+                if !path.exists() {
+                    eprintln!("No answers in {}", path.display());
+                }
+                answers.insert(path.clone(), Answers::load(&path)?);
+            }
+            (solve(solution, &data), answers.get(&path))
+        }
+        Err(_) => ([Outcome::NoInput, Outcome::NoInput], None),
+    };
+
+    let [part1, part2] = outcomes;
+    Ok([(1, part1), (2, part2)].map(|(part, outcome)| Check {
+        solution,
+        part,
+        expected: answers
+            .and_then(|answers| answers.get(solution.day, part))
+            .map(String::from),
+        outcome,
+    }))
+}
+
+fn print_table(checks: &[Check]) {
+    println!(
+        "{:4} {:>3}  {:10} {:>4}  {:7} {:20} Actual",
+        "Year", "Day", "Variant", "Part", "Result", "Expected"
+    );
+
+    for check in checks {
+        let status = match check.status() {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+        let actual = match &check.outcome {
+            Outcome::Answer(answer) => answer.clone(),
+            Outcome::NoInput => "(no input)".to_string(),
+            Outcome::Panicked(message) => format!("(panicked: {})", message),
+        };
+
+        println!(
+            "{:4} {:3}  {:10} {:4}  {:7} {:20} {}",
+            check.solution.year,
+            check.solution.day,
+            check.solution.variant,
+            check.part,
+            status,
+            check.expected.as_deref().unwrap_or("-"),
+            actual
+        );
+    }
+}
+
+// Runs the solutions on the real inputs and compares the answers with the answers files. Only
+// failures are an error, days without an input or an answer are reported as missing. The inputs
+// directly in AOC_INPUT_DIR are only used if all solutions are from a single year.
+pub fn verify(solutions: &[&'static Day], single_year: bool) -> Result<(), String> {
+    let mut answers = BTreeMap::new();
+
+    // The panics are reported in the table
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let checks = solutions
+        .iter()
+        .map(|solution| check(solution, single_year, &mut answers))
+        .flatten_ok()
+        .collect::<Result<Vec<_>, _>>();
+    std::panic::set_hook(default_hook);
+    let checks = checks?;
+
+    print_table(&checks);
+
+    let counts = checks.iter().counts_by(Check::status);
+    let count = |status| counts.get(&status).copied().unwrap_or(0);
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    );
+
+    match count(Status::Fail) {
+        0 => Ok(()),
+        failed => Err(format!("{} answers are wrong", failed)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let solution = &aoc2024::SOLUTIONS[0];
+        let status = |expected: Option<&str>, outcome: Outcome| {
+            Check {
+                solution,
+                part: 1,
+                expected: expected.map(String::from),
+                outcome,
+            }
+            .status()
+        };
+        let answer = |answer: &str| Outcome::Answer(answer.to_string());
+
+        assert_eq!(status(Some("42"), answer("42")), Status::Pass);
+        assert_eq!(status(Some("42"), answer("43")), Status::Fail);
+        assert_eq!(status(None, answer("42")), Status::Missing);
+        assert_eq!(status(Some("42"), Outcome::NoInput), Status::Missing);
+        assert_eq!(
+            status(None, Outcome::Panicked("oops".to_string())),
+            Status::Fail
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution = aoc2024::SOLUTIONS
+            .iter()
+            .find(|solution| solution.day == 1)
+            .unwrap();
+
+        assert_eq!(
+            solve(solution, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3"),
+            [
+                Outcome::Answer("11".to_string()),
+                Outcome::Answer("31".to_string())
+            ]
+        );
+
+        // the input is not a list of pairs of numbers
+        let [part1, part2] = solve(solution, "3");
+        assert!(matches!(part1, Outcome::Panicked(_)));
+        assert!(matches!(part2, Outcome::Panicked(_)));
+    }
+}